@@ -3,6 +3,7 @@ use std::path::Path;
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use eccodes::codes_handle::{CodesHandle, ProductKind};
+use eccodes::CodesNearestCache;
 
 pub fn key_reading(c: &mut Criterion) {
     //prepare the variables for benchmark
@@ -51,5 +52,32 @@ pub fn key_reading(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, key_reading);
+pub fn nearest_search(c: &mut Criterion) {
+    let file_path = Path::new("./data/iceland-levels.grib");
+    let product_kind = ProductKind::GRIB;
+
+    c.bench_function("nearest search per message", |b| {
+        b.iter(|| {
+            let mut handle = CodesHandle::new_from_file(file_path, product_kind).unwrap();
+
+            while let Some(msg) = handle.next().unwrap() {
+                let nearest = msg.codes_nearest().unwrap();
+                black_box(nearest.find_nearest(64.13, -21.89).unwrap());
+            }
+        })
+    });
+
+    c.bench_function("nearest search with cache", |b| {
+        b.iter(|| {
+            let mut handle = CodesHandle::new_from_file(file_path, product_kind).unwrap();
+            let mut cache = CodesNearestCache::new();
+
+            while let Some(msg) = handle.next().unwrap() {
+                black_box(cache.find_nearest(msg, 64.13, -21.89).unwrap());
+            }
+        })
+    });
+}
+
+criterion_group!(benches, key_reading, nearest_search);
 criterion_main!(benches);
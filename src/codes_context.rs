@@ -0,0 +1,151 @@
+//! Definition of `CodesContext` used for checking
+//! the state of ecCodes installation
+
+use std::{fs::read_dir, path::Path};
+
+use log::error;
+
+use crate::{
+    intermediate_bindings::{
+        codes_handle_delete, codes_handle_new_from_samples, codes_samples_path,
+    },
+    CodesError,
+};
+
+/// Sample used to check whether definitions are available
+const CHECK_SAMPLE: &str = "GRIB2";
+
+const DEFINITIONS_HINT: &str = "check that ecCodes is correctly installed \
+or set ECCODES_DEFINITION_PATH and ECCODES_SAMPLES_PATH environment variables";
+
+/// Extension of sample files in ecCodes samples directory
+const SAMPLE_EXTENSION: &str = "tmpl";
+
+/// Structure representing the default ecCodes context,
+/// shared by all handles created by this crate.
+///
+/// # Debugging
+///
+/// Verbosity of ecCodes internal diagnostics is controlled with the `ECCODES_DEBUG`
+/// environment variable (eg. `ECCODES_DEBUG=1`), which is read by ecCodes when the default context
+/// is created, so it must be set before the first use of this crate. ecCodes prints these
+/// diagnostics to the standard error, they are not passed to the [`log`] crate.
+///
+/// This crate reports its own diagnostics with [`log`]: calls of ecCodes destructor functions
+/// are logged at `trace` level and errors returned by them at `error` level, as destructors cannot return errors.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct CodesContext;
+
+impl CodesContext {
+    /// Checks whether ecCodes can load its definitions by creating a handle from a GRIB sample.
+    ///
+    /// Applications can call this function at startup to fail fast with a descriptive error,
+    /// instead of getting errors on the first read of a message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eccodes::CodesContext;
+    /// # fn main() -> anyhow::Result<()> {
+    /// CodesContext::check_definitions()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::DefinitionsNotFound`] when the sample handle cannot be created,
+    /// which indicates that ecCodes definitions or samples are not available.
+    pub fn check_definitions() -> Result<(), CodesError> {
+        let sample_handle = unsafe { codes_handle_new_from_samples(CHECK_SAMPLE) };
+
+        if sample_handle.is_null() {
+            return Err(CodesError::DefinitionsNotFound {
+                hint: DEFINITIONS_HINT,
+            });
+        }
+
+        unsafe {
+            codes_handle_delete(sample_handle).unwrap_or_else(|error| {
+                error!("codes_handle_delete() returned an error: {:?}", &error);
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Lists names of samples available in ecCodes samples path,
+/// which can be used to create new messages from samples (eg. `"GRIB2"`).
+///
+/// The samples path can consist of multiple directories separated with `:`
+/// (see `ECCODES_SAMPLES_PATH`). Directories that do not exist are skipped.
+/// Names are sorted and do not contain the `.tmpl` extension.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> anyhow::Result<()> {
+/// let samples = eccodes::available_samples()?;
+/// assert!(samples.iter().any(|sample| sample == "GRIB2"));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`CodesError::DefinitionsNotFound`] when ecCodes does not report any samples path.
+///
+/// Returns [`CodesError::FileHandlingInterrupted`] when any of samples directories cannot be read.
+pub fn available_samples() -> Result<Vec<String>, CodesError> {
+    let samples_path = unsafe { codes_samples_path()? }.ok_or(CodesError::DefinitionsNotFound {
+        hint: DEFINITIONS_HINT,
+    })?;
+
+    let mut samples = vec![];
+
+    for dir in samples_path
+        .split(':')
+        .map(Path::new)
+        .filter(|dir| dir.is_dir())
+    {
+        for entry in read_dir(dir)? {
+            let path = entry?.path();
+
+            if path.extension().is_some_and(|ext| ext == SAMPLE_EXTENSION) {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    samples.push(name.to_owned());
+                }
+            }
+        }
+    }
+
+    samples.sort_unstable();
+    samples.dedup();
+
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use super::{available_samples, CodesContext};
+
+    #[test]
+    fn definitions_available() -> Result<()> {
+        CodesContext::check_definitions()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn list_samples() -> Result<()> {
+        let samples = available_samples()?;
+
+        assert!(samples.iter().any(|sample| sample == "GRIB2"));
+        assert!(samples.iter().all(|sample| !sample.contains(".tmpl")));
+
+        Ok(())
+    }
+}
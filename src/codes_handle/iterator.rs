@@ -1,6 +1,11 @@
-use crate::{codes_handle::HandleGenerator, errors::CodesError, CodesHandle, KeyedMessage};
+use crate::{
+    codes_handle::{CodesFile, HandleGenerator, ProgressCallback},
+    errors::CodesError,
+    CodesHandle, DynamicKeyType, KeyedMessage,
+};
+use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
 use fallible_streaming_iterator::FallibleStreamingIterator;
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug, num::NonZeroUsize};
 
 /// # Errors
 ///
@@ -21,9 +26,18 @@ impl<S: HandleGenerator + Debug> FallibleStreamingIterator for CodesHandle<S> {
         } else {
             Some(KeyedMessage {
                 message_handle: new_eccodes_handle,
+                keys_namespace: self.keys_namespace.clone(),
             })
         };
 
+        if self.current_message.is_some() {
+            if let Some(progress) = self.progress.as_mut() {
+                (progress.0)(self.messages_read);
+            }
+
+            self.messages_read += 1;
+        }
+
         Ok(())
     }
 
@@ -32,15 +46,514 @@ impl<S: HandleGenerator + Debug> FallibleStreamingIterator for CodesHandle<S> {
     }
 }
 
+impl<S: HandleGenerator + Debug> CodesHandle<S> {
+    /// Sets a callback called with the index (starting from 0) of each message
+    /// read by the handle, which can be used to report progress when processing large files.
+    ///
+    /// The callback is called for all messages, including ones skipped with
+    /// [`nth_message()`](CodesHandle::nth_message). Setting a new callback replaces the previous one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eccodes::{CodesHandle, FallibleStreamingIterator, ProductKind};
+    /// # use std::path::Path;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland-surface.grib");
+    /// let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+    ///
+    /// handle.set_progress_callback(|index| println!("reading message {index}"));
+    ///
+    /// while let Some(_msg) = handle.next()? {
+    ///     // process the message
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_progress_callback(&mut self, callback: impl FnMut(usize) + 'static) {
+        self.progress = Some(ProgressCallback(Box::new(callback)));
+    }
+
+    /// Sets the namespace used by [`default_keys_iterator()`](KeyedMessage::default_keys_iterator)
+    /// of messages read by the handle after this call, eg. `"parameter"` or `"geography"`.
+    ///
+    /// Messages cloned from these messages keep the namespace. Empty namespace (`""`) restores
+    /// the default behaviour of iterating over all keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eccodes::{CodesHandle, FallibleIterator, FallibleStreamingIterator, ProductKind};
+    /// # use std::path::Path;
+    /// # use anyhow::Context;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland-surface.grib");
+    /// let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+    ///
+    /// handle.set_default_keys_namespace("parameter");
+    ///
+    /// let msg = handle.next()?.context("no message")?;
+    /// let mut keys_iter = msg.default_keys_iterator()?;
+    ///
+    /// while let Some(key_name) = keys_iter.next()? {
+    ///     println!("{key_name}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_default_keys_namespace(&mut self, namespace: &str) {
+        self.keys_namespace = Some(namespace.to_owned()).filter(|namespace| !namespace.is_empty());
+    }
+
+    /// Skips `n` messages and returns a clone of the next one as an owned [`KeyedMessage`],
+    /// so `nth_message(0)` returns the first unread message.
+    ///
+    /// Skipped messages are only generated and deleted, none of their keys are read.
+    /// The returned message stays current in the handle, so it is also available
+    /// with [`get()`](FallibleStreamingIterator::get).
+    ///
+    /// Returns `None` when there are fewer than `n + 1` messages left.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eccodes::{CodesHandle, KeyRead, ProductKind};
+    /// # use std::path::Path;
+    /// # use anyhow::Context;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland-surface.grib");
+    /// let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+    ///
+    /// let third = handle.nth_message(2)?.context("no message")?;
+    /// let short_name: String = third.read_key("shortName")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when internal ecCodes function returns non-zero code.
+    ///
+    /// Returns [`CodesError::CloneFailed`] when the message cannot be cloned.
+    pub fn nth_message(&mut self, n: usize) -> Result<Option<KeyedMessage>, CodesError> {
+        for _ in 0..=n {
+            self.advance()?;
+
+            if self.current_message.is_none() {
+                return Ok(None);
+            }
+        }
+
+        self.current_message
+            .as_ref()
+            .map(KeyedMessage::try_clone)
+            .transpose()
+    }
+
+    /// Returns clones of up to `n` next messages as owned [`KeyedMessage`]s, stopping early
+    /// without reading the rest of the source, eg. to preview a large file.
+    ///
+    /// Fewer messages are returned when there are fewer than `n` messages left.
+    /// The last returned message stays current in the handle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eccodes::{CodesHandle, ProductKind};
+    /// # use std::path::Path;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland-levels.grib");
+    /// let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+    ///
+    /// let preview = handle.take_messages(2)?;
+    /// assert_eq!(preview.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when internal ecCodes function returns non-zero code.
+    ///
+    /// Returns [`CodesError::CloneFailed`] when a message cannot be cloned.
+    pub fn take_messages(&mut self, n: usize) -> Result<Vec<KeyedMessage>, CodesError> {
+        let mut messages = vec![];
+
+        while messages.len() < n {
+            self.advance()?;
+
+            match &self.current_message {
+                Some(msg) => messages.push(msg.try_clone()?),
+                None => break,
+            }
+        }
+
+        Ok(messages)
+    }
+
+    /// Collects owned clones of all remaining messages and maps them with `f` in parallel,
+    /// returning the results in the order of messages.
+    ///
+    /// Messages are split into as many chunks as there are available CPUs and each chunk
+    /// is processed in a separate scoped thread. This is intended for CPU-bound processing
+    /// of independent messages, as all messages are read into memory before processing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eccodes::{CodesHandle, KeyRead, ProductKind};
+    /// # use std::path::Path;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland-levels.grib");
+    /// let handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+    ///
+    /// let maxima = handle.into_owned_messages_parallel(|msg| {
+    ///     let values: Vec<f64> = msg.read_key("values")?;
+    ///     Ok::<_, eccodes::CodesError>(values.into_iter().fold(f64::MIN, f64::max))
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when internal ecCodes function returns non-zero code while reading messages.
+    ///
+    /// Returns [`CodesError::CloneFailed`] when a message cannot be cloned.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `f` panics in any of the threads.
+    pub fn into_owned_messages_parallel<F, R>(mut self, f: F) -> Result<Vec<R>, CodesError>
+    where
+        F: Fn(KeyedMessage) -> R + Sync,
+        R: Send,
+    {
+        let mut messages = vec![];
+        while let Some(msg) = self.next()? {
+            messages.push(msg.try_clone()?);
+        }
+
+        let threads = std::thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let chunk_size = messages.len().div_ceil(threads).max(1);
+
+        let mut messages = messages.into_iter();
+        let mut chunks = vec![];
+        loop {
+            let chunk: Vec<KeyedMessage> = messages.by_ref().take(chunk_size).collect();
+            if chunk.is_empty() {
+                break;
+            }
+            chunks.push(chunk);
+        }
+
+        let f = &f;
+
+        Ok(std::thread::scope(|scope| {
+            let workers: Vec<_> = chunks
+                .into_iter()
+                .map(|chunk| scope.spawn(move || chunk.into_iter().map(f).collect::<Vec<R>>()))
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        }))
+    }
+
+    /// Iterates over remaining messages and groups their clones by the value of provided key,
+    /// converted to string with [`Display`](std::fmt::Display) of [`DynamicKeyType`].
+    ///
+    /// Messages within each group are in the order they appear in the source.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eccodes::{CodesHandle, ProductKind};
+    /// # use std::path::Path;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland-surface.grib");
+    /// let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+    ///
+    /// let by_name = handle.group_by_key("shortName")?;
+    /// assert!(by_name.contains_key("2d"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when the key cannot be read from any of messages
+    /// or when a message cannot be cloned.
+    pub fn group_by_key(
+        &mut self,
+        key_name: &str,
+    ) -> Result<HashMap<String, Vec<KeyedMessage>>, CodesError> {
+        let mut groups: HashMap<String, Vec<KeyedMessage>> = HashMap::new();
+
+        while let Some(msg) = self.next()? {
+            let value = msg.read_key_dynamic(key_name)?.to_string();
+            groups.entry(value).or_default().push(msg.try_clone()?);
+        }
+
+        Ok(groups)
+    }
+
+    /// Reads only provided keys from each of remaining messages and returns
+    /// one map per message, eg. to build a catalog of a large file.
+    ///
+    /// Data section is not decoded unless one of `keys` requires it,
+    /// so scanning header keys is much faster than reading all keys.
+    /// Keys not present in a message are omitted from its map.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eccodes::{CodesHandle, ProductKind};
+    /// # use std::path::Path;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland-levels.grib");
+    /// let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+    ///
+    /// let headers = handle.scan_headers(&["shortName", "level"])?;
+    /// println!("first message: {} at {}", headers[0]["shortName"], headers[0]["level"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when a message cannot be read from the source
+    /// or when a present key cannot be read.
+    pub fn scan_headers(
+        &mut self,
+        keys: &[&str],
+    ) -> Result<Vec<HashMap<String, DynamicKeyType>>, CodesError> {
+        let mut headers = vec![];
+
+        while let Some(msg) = self.next()? {
+            let mut header = HashMap::new();
+
+            for &key in keys {
+                if let Some(value) = msg.read_key_dynamic_if_present(key)? {
+                    header.insert(key.to_owned(), value);
+                }
+            }
+
+            headers.push(header);
+        }
+
+        Ok(headers)
+    }
+}
+
+/// Owning iterator over messages of [`CodesHandle`], created with
+/// [`into_fallible_iter()`](IntoFallibleIterator::into_fallible_iter).
+///
+/// Each message is cloned out of the handle, so it can be kept after
+/// the iterator advances and all [`FallibleIterator`] combinators can be used.
+///
+/// # Example
+///
+/// ```
+/// use eccodes::{CodesHandle, FallibleIterator, IntoFallibleIterator, KeyRead, ProductKind};
+/// # use std::path::Path;
+/// # fn main() -> anyhow::Result<()> {
+/// let file_path = Path::new("./data/iceland-surface.grib");
+/// let handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+///
+/// let short_names: Vec<String> = handle
+///     .into_fallible_iter()
+///     .map(|msg| msg.read_key("shortName"))
+///     .collect()?;
+///
+/// assert_eq!(short_names.len(), 5);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// The `next()` method will return [`CodesInternal`](crate::errors::CodesInternal)
+/// when internal ecCodes function returns non-zero code and [`CodesError::CloneFailed`]
+/// when the message cannot be cloned.
+#[derive(Debug)]
+pub struct CodesHandleIntoIter<S: HandleGenerator + Debug> {
+    handle: CodesHandle<S>,
+}
+
+impl<S: HandleGenerator + Debug> FallibleIterator for CodesHandleIntoIter<S> {
+    type Item = KeyedMessage;
+
+    type Error = CodesError;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, Self::Error> {
+        self.handle.next()?.map(KeyedMessage::try_clone).transpose()
+    }
+}
+
+impl<S: HandleGenerator + Debug> IntoFallibleIterator for CodesHandle<S> {
+    type Item = KeyedMessage;
+
+    type Error = CodesError;
+
+    type IntoFallibleIter = CodesHandleIntoIter<S>;
+
+    fn into_fallible_iter(self) -> Self::IntoFallibleIter {
+        CodesHandleIntoIter { handle: self }
+    }
+}
+
+/// Iterator over messages of [`CodesHandle`] created from file or memory, yielding each message
+/// together with its byte offset in the source. Created with
+/// [`message_iter_with_offsets()`](CodesHandle::message_iter_with_offsets).
+///
+/// The offset is the position of the stream before reading the message, so it can be used
+/// to read the message again or to build a custom index during a single pass over the file.
+///
+/// # Errors
+///
+/// The `next()` method will return [`CodesInternal`](crate::errors::CodesInternal)
+/// when internal ecCodes function returns non-zero code, [`CodesError::LibcNonZero`]
+/// when stream position cannot be read and [`CodesError::CloneFailed`] when the message cannot be cloned.
+#[derive(Debug)]
+pub struct MessagesWithOffsets<'a, D: Debug> {
+    handle: &'a mut CodesHandle<CodesFile<D>>,
+}
+
+impl<D: Debug> FallibleIterator for MessagesWithOffsets<'_, D> {
+    type Item = (u64, KeyedMessage);
+
+    type Error = CodesError;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, Self::Error> {
+        let offset = self.handle.source.stream_position()?;
+
+        self.handle
+            .next()?
+            .map(|msg| Ok((offset, msg.try_clone()?)))
+            .transpose()
+    }
+}
+
+/// Iterator over remaining messages of a file, created with [`CodesHandle::messages()`].
+///
+/// Messages are cloned out of the handle, as in [`into_fallible_iter()`](IntoFallibleIterator::into_fallible_iter).
+/// When the source is seekable, [`size_hint()`](FallibleIterator::size_hint) reports
+/// the number of messages left, so collecting can preallocate.
+#[derive(Debug)]
+pub struct Messages<'a, D: Debug> {
+    handle: &'a mut CodesHandle<CodesFile<D>>,
+    remaining: Option<usize>,
+}
+
+impl<D: Debug> FallibleIterator for Messages<'_, D> {
+    type Item = KeyedMessage;
+
+    type Error = CodesError;
+
+    fn next(&mut self) -> Result<Option<Self::Item>, Self::Error> {
+        let msg = self
+            .handle
+            .next()?
+            .map(KeyedMessage::try_clone)
+            .transpose()?;
+
+        if let Some(remaining) = &mut self.remaining {
+            *remaining = remaining.saturating_sub(1);
+        }
+
+        Ok(msg)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (0, None),
+        }
+    }
+}
+
+impl<D: Debug> CodesHandle<CodesFile<D>> {
+    /// Returns an iterator over remaining messages which knows how many messages are left.
+    ///
+    /// Messages left in the source are counted upfront with ecCodes and the stream
+    /// is moved back, so the count is available from [`size_hint()`](FallibleIterator::size_hint).
+    /// When the source is not seekable (eg. a pipe) the hint is unbounded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eccodes::{CodesHandle, FallibleIterator, ProductKind};
+    /// # use std::path::Path;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland-surface.grib");
+    /// let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+    ///
+    /// let messages = handle.messages()?;
+    /// assert_eq!(messages.size_hint(), (5, Some(5)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal) when messages cannot be counted
+    /// and [`CodesError::LibcNonZero`] when the stream cannot be moved back.
+    pub fn messages(&mut self) -> Result<Messages<'_, D>, CodesError> {
+        let remaining = self.source.remaining_messages()?;
+
+        Ok(Messages {
+            handle: self,
+            remaining,
+        })
+    }
+
+    /// Returns an iterator over remaining messages yielding `(offset, message)` pairs,
+    /// where `offset` is the byte position of the message in the source.
+    ///
+    /// Messages are cloned out of the handle, as in [`into_fallible_iter()`](IntoFallibleIterator::into_fallible_iter).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eccodes::{CodesHandle, FallibleIterator, ProductKind};
+    /// # use std::path::Path;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland-surface.grib");
+    /// let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+    ///
+    /// let offsets: Vec<u64> = handle
+    ///     .message_iter_with_offsets()
+    ///     .map(|(offset, _msg)| Ok(offset))
+    ///     .collect()?;
+    ///
+    /// assert_eq!(offsets[0], 0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn message_iter_with_offsets(&mut self) -> MessagesWithOffsets<'_, D> {
+        MessagesWithOffsets { handle: self }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         codes_handle::{CodesHandle, ProductKind},
-        DynamicKeyType,
+        DynamicKeyType, FallibleIterator, IntoFallibleIterator, KeyRead, KeyedMessage,
     };
     use anyhow::{Context, Ok, Result};
     use fallible_streaming_iterator::FallibleStreamingIterator;
-    use std::path::Path;
+    use std::{fs::read, path::Path};
 
     #[test]
     fn iterator_lifetimes() -> Result<()> {
@@ -177,4 +690,240 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn into_fallible_iter_collected() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let product_kind = ProductKind::GRIB;
+        let handle = CodesHandle::new_from_file(file_path, product_kind)?;
+
+        let levels = handle
+            .into_fallible_iter()
+            .map(|msg| msg.read_key_dynamic("level"))
+            .collect::<Vec<_>>()?;
+
+        assert_eq!(levels.len(), 5);
+
+        for level in levels {
+            match level {
+                DynamicKeyType::Int(_) => {}
+                _ => panic!("Incorrect variant of int key"),
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn nth_message() -> Result<()> {
+        let file_path = Path::new("./data/iceland-levels.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut reference = CodesHandle::new_from_file(file_path, product_kind)?;
+        reference.next()?;
+        reference.next()?;
+        let expected = reference
+            .next()?
+            .context("Message not some")?
+            .read_key_dynamic("level")?;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let msg = handle.nth_message(2)?.context("Message not some")?;
+
+        assert_eq!(msg.read_key_dynamic("level")?, expected);
+        let current = handle.get().context("Message not some")?;
+        assert_eq!(current.read_key_dynamic("level")?, expected);
+
+        assert!(handle.nth_message(1000)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn take_messages() -> Result<()> {
+        let file_path = Path::new("./data/iceland-levels.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let messages = handle.take_messages(2)?;
+
+        assert_eq!(messages.len(), 2);
+        let current = handle.get().context("Message not some")?;
+        assert_eq!(current.data_hash()?, messages[1].data_hash()?);
+
+        let mut reference = CodesHandle::new_from_file(file_path, product_kind)?;
+        for msg in &messages {
+            let expected = reference.next()?.context("Message not some")?;
+            assert_eq!(msg.data_hash()?, expected.data_hash()?);
+        }
+
+        let mut handle =
+            CodesHandle::new_from_file(Path::new("./data/iceland.grib"), product_kind)?;
+        let all_messages = handle.take_messages(usize::MAX)?;
+        assert!(!all_messages.is_empty());
+        assert!(handle.take_messages(1)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn group_by_key() -> Result<()> {
+        let file_path = Path::new("./data/iceland-levels.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut reference = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut levels = vec![];
+        let mut count = 0;
+        while let Some(msg) = reference.next()? {
+            let level = msg.read_key_dynamic("level")?.to_string();
+            if !levels.contains(&level) {
+                levels.push(level);
+            }
+            count += 1;
+        }
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let groups = handle.group_by_key("level")?;
+
+        assert_eq!(groups.len(), levels.len());
+        assert_eq!(groups.values().map(Vec::len).sum::<usize>(), count);
+
+        for (level, messages) in &groups {
+            for msg in messages {
+                assert_eq!(&msg.read_key_dynamic("level")?.to_string(), level);
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn messages_size_hint() -> Result<()> {
+        let file_path = Path::new("./data/iceland-levels.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut reference = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut count = 0;
+        while reference.next()?.is_some() {
+            count += 1;
+        }
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        handle.next()?;
+
+        let mut messages = handle.messages()?;
+        assert_eq!(messages.size_hint(), (count - 1, Some(count - 1)));
+
+        messages.next()?.context("Message not some")?;
+        assert_eq!(messages.size_hint(), (count - 2, Some(count - 2)));
+
+        let collected: Vec<KeyedMessage> = messages.collect()?;
+        assert_eq!(collected.len(), count - 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn owned_messages_parallel() -> Result<()> {
+        let file_path = Path::new("./data/iceland-levels.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mean = |msg: &KeyedMessage| -> Result<f64> {
+            let values: Vec<f64> = msg.read_key("values")?;
+            #[allow(clippy::cast_precision_loss)]
+            Ok(values.iter().sum::<f64>() / values.len() as f64)
+        };
+
+        let mut reference = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut expected = vec![];
+        while let Some(msg) = reference.next()? {
+            expected.push(mean(msg)?);
+        }
+
+        let handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let means = handle
+            .into_owned_messages_parallel(|msg| mean(&msg))?
+            .into_iter()
+            .collect::<Result<Vec<f64>>>()?;
+
+        assert_eq!(means.len(), expected.len());
+        assert!(means
+            .iter()
+            .zip(&expected)
+            .all(|(mean, expected)| (mean - expected).abs() < 1e-9));
+
+        Ok(())
+    }
+
+    #[test]
+    fn scan_headers() -> Result<()> {
+        let file_path = Path::new("./data/iceland-levels.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let headers = handle.scan_headers(&["shortName", "level", "nonExistingKey"])?;
+
+        let mut reference = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut count = 0;
+        while let Some(msg) = reference.next()? {
+            let header = &headers[count];
+
+            assert_eq!(header.len(), 2);
+            assert_eq!(header["shortName"], msg.read_key_dynamic("shortName")?);
+            assert_eq!(header["level"], msg.read_key_dynamic("level")?);
+
+            count += 1;
+        }
+
+        assert_eq!(headers.len(), count);
+
+        Ok(())
+    }
+
+    #[test]
+    fn progress_callback() -> Result<()> {
+        use std::{cell::RefCell, rc::Rc};
+
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let product_kind = ProductKind::GRIB;
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+
+        let indices = Rc::new(RefCell::new(vec![]));
+        let callback_indices = Rc::clone(&indices);
+        handle.set_progress_callback(move |index| callback_indices.borrow_mut().push(index));
+
+        let mut count = 0;
+        while handle.next()?.is_some() {
+            count += 1;
+        }
+
+        assert_eq!(count, 5);
+        assert_eq!(*indices.borrow(), vec![0, 1, 2, 3, 4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn message_offsets() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let product_kind = ProductKind::GRIB;
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+
+        let messages: Vec<(u64, KeyedMessage)> = handle.message_iter_with_offsets().collect()?;
+        assert_eq!(messages.len(), 5);
+        assert_eq!(messages[0].0, 0);
+        assert!(messages.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        let file_data = read(file_path)?;
+
+        for (offset, msg) in &messages {
+            let tail = file_data[usize::try_from(*offset)?..].to_vec();
+            let mut reopened = CodesHandle::new_from_memory(tail, product_kind)?;
+            let reopened_msg = reopened.next()?.context("Message not some")?;
+
+            assert_eq!(reopened_msg.data_hash()?, msg.data_hash()?);
+        }
+
+        Ok(())
+    }
 }
@@ -4,32 +4,67 @@
 #[cfg(feature = "experimental_index")]
 use crate::codes_index::CodesIndex;
 use crate::{
-    intermediate_bindings::codes_handle_new_from_file, pointer_guard, CodesError, KeyedMessage,
+    intermediate_bindings::{codes_count_in_file, codes_handle_new_from_file},
+    pointer_guard, CodesError, KeyedMessage,
 };
 use eccodes_sys::{codes_handle, ProductKind_PRODUCT_GRIB};
 use errno::errno;
+use fallible_streaming_iterator::FallibleStreamingIterator;
 use libc::{c_char, c_void, size_t, FILE};
+use log::error;
 use std::{
     fmt::Debug,
     fs::{File, OpenOptions},
     os::unix::prelude::AsRawFd,
-    path::Path,
+    path::{Path, PathBuf},
+    ptr::null_mut,
 };
 
+#[cfg(feature = "flate2")]
+use std::io::Read;
+
 mod iterator;
+#[cfg(feature = "futures")]
+mod stream;
+mod validate;
+
+pub use iterator::{CodesHandleIntoIter, Messages, MessagesWithOffsets};
+#[cfg(feature = "futures")]
+pub use stream::MessageStream;
+pub use validate::ValidationReport;
+
+/// First two bytes of every gzip stream
+#[cfg(feature = "flate2")]
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
 
 /// This is an internal structure used to access provided file by `CodesHandle`.
 /// It also allows to differentiate between `CodesHandle` created from file and from index.
 /// It is not intended to be used directly by the user.
 #[doc(hidden)]
-#[derive(Debug)]
 pub struct CodesFile<D> {
     // fields dropped from top
     pointer: *mut FILE,
     product_kind: ProductKind,
+    path: Option<PathBuf>,
     _data: D,
 }
 
+/// Prints the product kind and the path of the file (or `memory` for buffers),
+/// as stream pointer and data are not meaningful to the user.
+impl<D> Debug for CodesFile<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = match &self.path {
+            Some(path) => path.display().to_string(),
+            None => "memory".to_owned(),
+        };
+
+        f.debug_struct("CodesFile")
+            .field("product_kind", &self.product_kind)
+            .field("source", &source)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Internal trait implemented for types that can be called to generate `*mut codes_handle`.
 #[doc(hidden)]
 pub trait HandleGenerator {
@@ -42,12 +77,80 @@ impl<D> HandleGenerator for CodesFile<D> {
     }
 }
 
+impl<D> CodesFile<D> {
+    /// Returns the data the stream reads from.
+    #[cfg(test)]
+    #[allow(clippy::used_underscore_binding)] // the field is otherwise only held to keep the data alive
+    fn data(&self) -> &D {
+        &self._data
+    }
+
+    /// Returns current position of the stream in bytes.
+    fn stream_position(&self) -> Result<u64, CodesError> {
+        let position = unsafe { libc::ftello(self.pointer) };
+
+        u64::try_from(position).map_err(|_| libc_error("ftello"))
+    }
+
+    /// Moves the stream back to the beginning.
+    fn rewind(&mut self) -> Result<(), CodesError> {
+        self.seek(0)
+    }
+
+    /// Moves the stream to the given byte position.
+    fn seek(&mut self, position: u64) -> Result<(), CodesError> {
+        let offset = libc::off_t::try_from(position)
+            .map_err(|_| CodesError::StreamPositionOutOfRange(position))?;
+        let result = unsafe { libc::fseeko(self.pointer, offset, libc::SEEK_SET) };
+
+        if result != 0 {
+            return Err(libc_error("fseeko"));
+        }
+
+        Ok(())
+    }
+
+    /// Counts messages left in the stream, without changing its position.
+    ///
+    /// Returns `None` when the stream is not seekable (eg. a pipe).
+    fn remaining_messages(&mut self) -> Result<Option<usize>, CodesError> {
+        let Ok(position) = self.stream_position() else {
+            return Ok(None);
+        };
+
+        let count = unsafe { codes_count_in_file(self.pointer) };
+        self.seek(position)?;
+
+        Ok(Some(count?))
+    }
+}
+
+#[doc(hidden)]
+impl<D> Drop for CodesFile<D> {
+    /// Closes the stream before `_data` is dropped, so the stream never outlives
+    /// the buffer (or file descriptor) it reads from.
+    ///
+    /// Streams created from files own a duplicated file descriptor,
+    /// so closing them does not interfere with the destructor of `fs::File`.
+    fn drop(&mut self) {
+        if !self.pointer.is_null() && unsafe { libc::fclose(self.pointer) } != 0 {
+            error!("fclose() returned an error: {:?}", errno());
+        }
+
+        self.pointer = null_mut();
+    }
+}
+
 /// Structure providing access to the GRIB file which takes a full ownership of the accessed file.
 ///  
 /// It can be constructed from:
 ///
 /// - File path using [`new_from_file()`](CodesHandle::new_from_file)
+///   or [`new_from_file_with_mode()`](CodesHandle::new_from_file_with_mode)
 /// - From memory buffer using [`new_from_memory()`](CodesHandle::new_from_memory)
+/// - Borrowed memory buffer using [`new_from_slice()`](CodesHandle::new_from_slice)
+/// - Gzip-compressed file path using [`new_from_gzip_file()`](CodesHandle::new_from_gzip_file) (with `flate2` feature enabled)
+/// - Memory-mapped file path using [`new_from_mmap()`](CodesHandle::new_from_mmap) (with `memmap2` feature enabled)
 /// - From GRIB index using [`new_from_index()`](CodesHandle::new_from_index) (with `experimental_index` feature enabled)
 ///
 /// Destructor for this structure does not panic, but some internal functions may rarely fail
@@ -118,17 +221,41 @@ impl<D> HandleGenerator for CodesFile<D> {
 /// ```
 ///
 /// All available methods for `CodesHandle` iterator can be found in [`FallibleStreamingIterator`](crate::FallibleStreamingIterator) trait.
-#[derive(Debug)]
+///
+/// `CodesHandle` also implements [`IntoFallibleIterator`](crate::IntoFallibleIterator), which yields
+/// cloned (owned) messages and gives access to all [`FallibleIterator`](crate::FallibleIterator) combinators.
 pub struct CodesHandle<S: Debug + HandleGenerator> {
     // fields are dropped from top to bottom
     current_message: Option<KeyedMessage>,
     source: S,
+    progress: Option<ProgressCallback>,
+    messages_read: usize,
+    keys_namespace: Option<String>,
+}
+
+/// Wrapper of user-provided progress callback
+struct ProgressCallback(Box<dyn FnMut(usize)>);
+
+/// Prints the source and the iteration state without raw pointers of the current message.
+impl<S: Debug + HandleGenerator> Debug for CodesHandle<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CodesHandle")
+            .field("source", &self.source)
+            .field("messages_read", &self.messages_read)
+            .field("keys_namespace", &self.keys_namespace)
+            .finish_non_exhaustive()
+    }
 }
 
 // 2024-07-26
 // Previously CodesHandle had implemented Drop which called libc::fclose()
 // but that closed the file descriptor and interfered with rust's fs::file destructor.
 //
+// Update: not closing the stream leaked the FILE created by fmemopen() and left it
+// pointing to the already freed buffer. Now CodesFile closes the stream in its own destructor,
+// which runs before its fields are dropped, and streams created from files use a duplicated
+// file descriptor, so fs::File still closes only its own descriptor.
+//
 // To my best understanding the purpose of destructor is to clear memory and remove
 // any pointers that would be dangling.
 //
@@ -147,6 +274,30 @@ pub enum ProductKind {
     GRIB = ProductKind_PRODUCT_GRIB as isize,
 }
 
+/// Enum representing the mode in which the file stream is opened
+/// by [`new_from_file_with_mode()`](CodesHandle::new_from_file_with_mode).
+///
+/// Each variant maps to a valid, nul-terminated `fdopen()` mode string,
+/// so arbitrary modes cannot be passed to libc.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum OpenMode {
+    /// Read-only stream (`"r"`)
+    #[default]
+    Read,
+    /// Read-only stream explicitly opened in binary mode (`"rb"`),
+    /// for platforms which distinguish text and binary streams
+    ReadBinary,
+}
+
+impl OpenMode {
+    fn as_c_mode(self) -> &'static [u8] {
+        match self {
+            OpenMode::Read => b"r\0",
+            OpenMode::ReadBinary => b"rb\0",
+        }
+    }
+}
+
 impl CodesHandle<CodesFile<File>> {
     ///Opens file at given [`Path`] as selected [`ProductKind`] and contructs `CodesHandle`.
     ///
@@ -175,7 +326,7 @@ impl CodesHandle<CodesFile<File>> {
     /// The file stream and [`File`] are safely closed when `CodesHandle` is dropped.
     ///
     ///## Errors
-    ///Returns [`CodesError::FileHandlingInterrupted`] with [`io::Error`](std::io::Error)
+    ///Returns [`CodesError::FileOpen`] with the path and [`io::Error`](std::io::Error)
     ///when the file cannot be opened.
     ///
     ///Returns [`CodesError::LibcNonZero`] with [`errno`](errno::Errno) information
@@ -187,16 +338,93 @@ impl CodesHandle<CodesFile<File>> {
         file_path: P,
         product_kind: ProductKind,
     ) -> Result<Self, CodesError> {
-        let file = OpenOptions::new().read(true).open(file_path)?;
-        let file_pointer = open_with_fdopen(&file)?;
+        Self::new_from_file_with_mode(file_path, product_kind, OpenMode::default())
+    }
+
+    ///Same as [`new_from_file()`](CodesHandle::new_from_file) but checks that the file
+    ///contains at least one message of given [`ProductKind`].
+    ///
+    ///ecCodes skips any data that is not a message, so a file that is not GRIB at all
+    ///(eg. an HTML error page saved as `.grib`) otherwise results in a handle that yields no messages.
+    ///The first message is decoded to check the file, and the stream is moved back to its beginning,
+    ///so the handle yields all messages.
+    ///
+    ///## Example
+    ///
+    ///```
+    ///# use eccodes::codes_handle::{ProductKind, CodesHandle};
+    ///# use std::path::Path;
+    ///# fn main() -> anyhow::Result<()> {
+    ///let file_path = Path::new("./data/iceland.grib");
+    ///
+    ///let handle = CodesHandle::new_from_file_validated(file_path, ProductKind::GRIB)?;
+    /// # Ok(())
+    /// # }
+    ///```
+    ///
+    ///## Errors
+    ///Returns [`CodesError::NoMessages`] when the file does not contain any message.
+    ///
+    ///Returns the same errors as [`new_from_file()`](CodesHandle::new_from_file)
+    ///and [`CodesError::LibcNonZero`] when the stream cannot be moved back to its beginning.
+    pub fn new_from_file_validated<P: AsRef<Path>>(
+        file_path: P,
+        product_kind: ProductKind,
+    ) -> Result<Self, CodesError> {
+        let mut handle = Self::new_from_file(file_path, product_kind)?;
+
+        handle.advance()?;
+        if handle.current_message.take().is_none() {
+            return Err(CodesError::NoMessages);
+        }
+
+        handle.source.rewind()?;
+        handle.messages_read = 0;
+
+        Ok(handle)
+    }
+
+    ///Same as [`new_from_file()`](CodesHandle::new_from_file) but the file stream
+    ///is opened with provided [`OpenMode`].
+    ///
+    ///## Example
+    ///
+    ///```
+    ///# use eccodes::codes_handle::{CodesHandle, OpenMode, ProductKind};
+    ///# use std::path::Path;
+    ///# fn main() -> anyhow::Result<()> {
+    ///let file_path = Path::new("./data/iceland.grib");
+    ///
+    ///let handle = CodesHandle::new_from_file_with_mode(file_path, ProductKind::GRIB, OpenMode::ReadBinary)?;
+    /// # Ok(())
+    /// # }
+    ///```
+    ///
+    ///## Errors
+    ///Returns [`CodesError::FileOpen`] with the path and [`io::Error`](std::io::Error)
+    ///when the file cannot be opened.
+    ///
+    ///Returns [`CodesError::LibcNonZero`] with [`errno`](errno::Errno) information
+    ///when the stream cannot be created from the file descriptor.
+    pub fn new_from_file_with_mode<P: AsRef<Path>>(
+        file_path: P,
+        product_kind: ProductKind,
+        mode: OpenMode,
+    ) -> Result<Self, CodesError> {
+        let file = open_read_only(file_path.as_ref())?;
+        let file_pointer = open_with_fdopen(&file, mode)?;
 
         Ok(Self {
             source: CodesFile {
                 _data: file,
                 pointer: file_pointer,
                 product_kind,
+                path: Some(file_path.as_ref().to_path_buf()),
             },
             current_message: None,
+            progress: None,
+            messages_read: 0,
+            keys_namespace: None,
         })
     }
 }
@@ -246,8 +474,170 @@ impl CodesHandle<CodesFile<Vec<u8>>> {
                 _data: file_data,
                 product_kind,
                 pointer: file_pointer,
+                path: None,
+            },
+            current_message: None,
+            progress: None,
+            messages_read: 0,
+            keys_namespace: None,
+        })
+    }
+
+    ///Opens gzip-compressed file at given [`Path`] as selected [`ProductKind`] and contructs `CodesHandle`.
+    ///
+    ///## Example
+    ///
+    ///```no_run
+    ///# use eccodes::codes_handle::{ProductKind, CodesHandle};
+    ///# use std::path::Path;
+    ///# fn main() -> anyhow::Result<()> {
+    ///let file_path = Path::new("./data/iceland.grib.gz");
+    ///let product_kind = ProductKind::GRIB;
+    ///
+    ///let handle = CodesHandle::new_from_gzip_file(file_path, product_kind)?;
+    /// # Ok(())
+    /// # }
+    ///```
+    ///
+    ///The function reads the whole file, checks the gzip magic bytes,
+    ///decompresses the data into a buffer and then delegates to [`new_from_memory()`](CodesHandle::new_from_memory).
+    ///Therefore the whole decompressed file is kept in memory for the lifetime of the `CodesHandle`.
+    ///
+    ///## Errors
+    ///Returns [`CodesError::FileOpen`] with the path and [`io::Error`](std::io::Error)
+    ///when the file cannot be read.
+    ///
+    ///Returns [`CodesError::FileHandlingInterrupted`] with [`io::Error`](std::io::Error)
+    ///when the file cannot be decompressed.
+    ///
+    ///Returns [`CodesError::NotGzipped`] when the file does not start with gzip magic bytes.
+    ///
+    ///Returns [`CodesError::LibcNonZero`] with [`errno`](errno::Errno) information
+    ///when the file stream cannot be created.
+    #[cfg(feature = "flate2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "flate2")))]
+    pub fn new_from_gzip_file<P: AsRef<Path>>(
+        file_path: P,
+        product_kind: ProductKind,
+    ) -> Result<Self, CodesError> {
+        let compressed =
+            std::fs::read(file_path.as_ref()).map_err(|source| CodesError::FileOpen {
+                path: file_path.as_ref().to_path_buf(),
+                source,
+            })?;
+
+        if !compressed.starts_with(&GZIP_MAGIC) {
+            return Err(CodesError::NotGzipped);
+        }
+
+        let mut file_data = vec![];
+        flate2::read::GzDecoder::new(compressed.as_slice()).read_to_end(&mut file_data)?;
+
+        Self::new_from_memory(file_data, product_kind)
+    }
+}
+
+impl<'a> CodesHandle<CodesFile<&'a [u8]>> {
+    ///Opens data in borrowed buffer as selected [`ProductKind`] and contructs `CodesHandle`.
+    ///
+    ///## Example
+    ///
+    ///```
+    ///# use eccodes::{ProductKind, CodesHandle};
+    ///# fn main() -> anyhow::Result<()> {
+    ///let file_data = std::fs::read("./data/iceland.grib")?;
+    ///
+    ///let handle = CodesHandle::new_from_slice(&file_data, ProductKind::GRIB)?;
+    /// # Ok(())
+    /// # }
+    ///```
+    ///
+    ///Same as [`new_from_memory()`](CodesHandle::new_from_memory) but does not take ownership
+    ///of the data, which avoids copying the buffer when the caller needs to keep it.
+    ///The handle borrows the buffer, so the buffer cannot be dropped or modified while the handle exists.
+    ///
+    ///## Errors
+    ///Returns [`CodesError::LibcNonZero`] with [`errno`](errno::Errno) information
+    ///when the file stream cannot be created.
+    pub fn new_from_slice(
+        file_data: &'a [u8],
+        product_kind: ProductKind,
+    ) -> Result<Self, CodesError> {
+        let file_pointer = open_with_fmemopen(file_data)?;
+
+        Ok(Self {
+            source: CodesFile {
+                _data: file_data,
+                product_kind,
+                pointer: file_pointer,
+                path: None,
             },
             current_message: None,
+            progress: None,
+            messages_read: 0,
+            keys_namespace: None,
+        })
+    }
+}
+
+#[cfg(feature = "memmap2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "memmap2")))]
+impl CodesHandle<CodesFile<memmap2::Mmap>> {
+    ///Maps file at given [`Path`] into memory and contructs `CodesHandle` with selected [`ProductKind`].
+    ///
+    ///## Example
+    ///
+    ///```
+    ///# use eccodes::codes_handle::{ProductKind, CodesHandle};
+    ///# use std::path::Path;
+    ///# fn main() -> anyhow::Result<()> {
+    ///let file_path = Path::new("./data/iceland-levels.grib");
+    ///let product_kind = ProductKind::GRIB;
+    ///
+    ///let handle = CodesHandle::new_from_mmap(file_path, product_kind)?;
+    /// # Ok(())
+    /// # }
+    ///```
+    ///
+    ///The function maps the file with [`memmap2::Mmap`] and associates the mapped region with a stream
+    ///represented by [`libc::FILE`](https://docs.rs/libc/0.2.101/libc/enum.FILE.html) pointer
+    ///using [`fmemopen()`](https://man7.org/linux/man-pages/man3/fmemopen.3.html).
+    ///This gives access to the file paged by the operating system, without reading it whole into memory,
+    ///which can be beneficial for very large files.
+    ///
+    ///The memory map is owned by `CodesHandle` and is kept alive until the handle is dropped.
+    ///Note that modifying or truncating the file while it is mapped leads to undefined behaviour.
+    ///
+    ///## Errors
+    ///Returns [`CodesError::FileOpen`] with the path and [`io::Error`](std::io::Error)
+    ///when the file cannot be opened.
+    ///
+    ///Returns [`CodesError::FileHandlingInterrupted`] with [`io::Error`](std::io::Error)
+    ///when the file cannot be mapped.
+    ///
+    ///Returns [`CodesError::LibcNonZero`] with [`errno`](errno::Errno) information
+    ///when the file stream cannot be created.
+    pub fn new_from_mmap<P: AsRef<Path>>(
+        file_path: P,
+        product_kind: ProductKind,
+    ) -> Result<Self, CodesError> {
+        let file = open_read_only(file_path.as_ref())?;
+        // SAFETY: the map is read-only and it is owned by CodesHandle
+        // so it outlives the stream created from it
+        let file_data = unsafe { memmap2::Mmap::map(&file)? };
+        let file_pointer = open_with_fmemopen(&file_data)?;
+
+        Ok(Self {
+            source: CodesFile {
+                _data: file_data,
+                product_kind,
+                pointer: file_pointer,
+                path: Some(file_path.as_ref().to_path_buf()),
+            },
+            current_message: None,
+            progress: None,
+            messages_read: 0,
+            keys_namespace: None,
         })
     }
 }
@@ -287,19 +677,51 @@ impl CodesHandle<CodesIndex> {
         let new_handle = CodesHandle {
             source: index,
             current_message: None,
+            progress: None,
+            messages_read: 0,
+            keys_namespace: None,
         };
 
         Ok(new_handle)
     }
 }
 
-fn open_with_fdopen(file: &File) -> Result<*mut FILE, CodesError> {
-    let file_ptr = unsafe { libc::fdopen(file.as_raw_fd(), "r".as_ptr().cast::<c_char>()) };
+fn open_read_only(file_path: &Path) -> Result<File, CodesError> {
+    OpenOptions::new()
+        .read(true)
+        .open(file_path)
+        .map_err(|source| CodesError::FileOpen {
+            path: file_path.to_path_buf(),
+            source,
+        })
+}
+
+/// Constructs [`CodesError::LibcNonZero`] from current `errno` for the failed libc function.
+fn libc_error(op: &'static str) -> CodesError {
+    let errno = errno();
+
+    CodesError::LibcNonZero {
+        code: errno.0,
+        errno,
+        op,
+    }
+}
+
+fn open_with_fdopen(file: &File, mode: OpenMode) -> Result<*mut FILE, CodesError> {
+    // the stream gets its own descriptor, which is closed together with the stream
+    let file_descriptor = unsafe { libc::dup(file.as_raw_fd()) };
+
+    if file_descriptor < 0 {
+        return Err(libc_error("dup"));
+    }
+
+    let file_ptr =
+        unsafe { libc::fdopen(file_descriptor, mode.as_c_mode().as_ptr().cast::<c_char>()) };
 
     if file_ptr.is_null() {
-        let error_val = errno();
-        let error_code = error_val.0;
-        return Err(CodesError::LibcNonZero(error_code, error_val));
+        let error = libc_error("fdopen");
+        unsafe { libc::close(file_descriptor) };
+        return Err(error);
     }
 
     Ok(file_ptr)
@@ -314,14 +736,12 @@ fn open_with_fmemopen(file_data: &[u8]) -> Result<*mut FILE, CodesError> {
         file_ptr = libc::fmemopen(
             file_data_ptr,
             file_data.len() as size_t,
-            "r".as_ptr().cast::<c_char>(),
+            OpenMode::Read.as_c_mode().as_ptr().cast::<c_char>(),
         );
     }
 
     if file_ptr.is_null() {
-        let error_val = errno();
-        let error_code = error_val.0;
-        return Err(CodesError::LibcNonZero(error_code, error_val));
+        return Err(libc_error("fmemopen"));
     }
 
     Ok(file_ptr)
@@ -329,13 +749,18 @@ fn open_with_fmemopen(file_data: &[u8]) -> Result<*mut FILE, CodesError> {
 
 #[cfg(test)]
 mod tests {
-    use crate::codes_handle::{CodesHandle, ProductKind};
+    use crate::codes_handle::{open_with_fmemopen, CodesHandle, OpenMode, ProductKind};
     #[cfg(feature = "experimental_index")]
     use crate::codes_index::{CodesIndex, Select};
+    use crate::CodesError;
     use anyhow::{Context, Result};
     use eccodes_sys::ProductKind_PRODUCT_GRIB;
     use fallible_streaming_iterator::FallibleStreamingIterator;
-    use std::{fs::File, io::Read, path::Path};
+    use std::{
+        fs::{remove_file, write, File},
+        io::Read,
+        path::Path,
+    };
 
     #[test]
     fn file_constructor() -> Result<()> {
@@ -350,11 +775,88 @@ mod tests {
             ProductKind_PRODUCT_GRIB
         });
 
-        handle.source._data.metadata()?;
+        handle.source.data().metadata()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn debug_without_pointers() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        handle.next()?;
+        let debug = format!("{handle:?}");
+
+        assert!(debug.contains("GRIB"));
+        assert!(debug.contains("iceland.grib"));
+        assert!(!debug.contains("0x"));
+
+        let file_data = std::fs::read(file_path)?;
+        let handle = CodesHandle::new_from_memory(file_data, product_kind)?;
+        let debug = format!("{handle:?}");
+
+        assert!(debug.contains("memory"));
+        assert!(!debug.contains("0x"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn validated_file_constructor() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file_validated(file_path, ProductKind::GRIB)?;
+
+        let mut count = 0;
+        while handle.next()?.is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 5);
+
+        let not_grib_path = Path::new("./data/not_grib.grib");
+        write(not_grib_path, "<html><body>404 Not Found</body></html>")?;
+        let result = CodesHandle::new_from_file_validated(not_grib_path, ProductKind::GRIB);
+        remove_file(not_grib_path)?;
+
+        assert!(matches!(result, Err(CodesError::NoMessages)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn file_constructor_binary_mode() -> Result<()> {
+        use crate::KeyRead;
+
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle =
+            CodesHandle::new_from_file_with_mode(file_path, product_kind, OpenMode::ReadBinary)?;
+        assert!(!handle.source.pointer.is_null());
+
+        let msg = handle.next()?.context("Message not some")?;
+        let short_name: String = msg.read_key("shortName")?;
+        assert_eq!(short_name, "msl");
 
         Ok(())
     }
 
+    #[test]
+    fn file_constructor_missing_path() {
+        let file_path = Path::new("./data/does-not-exist.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let result = CodesHandle::new_from_file(file_path, product_kind);
+
+        match result {
+            Err(err @ CodesError::FileOpen { .. }) => {
+                assert!(err.to_string().contains("./data/does-not-exist.grib"));
+            }
+            _ => panic!("Expected FileOpen error"),
+        }
+    }
+
     #[test]
     fn memory_constructor() -> Result<()> {
         let product_kind = ProductKind::GRIB;
@@ -370,7 +872,161 @@ mod tests {
             ProductKind_PRODUCT_GRIB
         });
 
-        assert!(!handle.source._data.is_empty());
+        assert!(!handle.source.data().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn fmemopen_error_names_function() {
+        // empty buffers are rejected by some libc implementations,
+        // on others the stream is created and there is nothing to check
+        match open_with_fmemopen(&[]) {
+            Err(CodesError::LibcNonZero { op, .. }) => assert_eq!(op, "fmemopen"),
+            Err(err) => panic!("Unexpected error {err}"),
+            Ok(file_ptr) => unsafe {
+                libc::fclose(file_ptr);
+            },
+        }
+    }
+
+    #[test]
+    fn seek_out_of_range() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+
+        let result = handle.source.seek(u64::MAX);
+        assert!(matches!(
+            result,
+            Err(CodesError::StreamPositionOutOfRange(u64::MAX))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn memory_handles_dropped_in_loop() -> Result<()> {
+        use crate::KeyRead;
+
+        let mut f = File::open(Path::new("./data/iceland-surface.grib"))?;
+        let mut buf = Vec::new();
+        f.read_to_end(&mut buf)?;
+
+        // each stream must be closed before its buffer is freed,
+        // otherwise freed buffers are reused by next handles while streams still point to them
+        for _ in 0..1000 {
+            let mut handle = CodesHandle::new_from_memory(buf.clone(), ProductKind::GRIB)?;
+            let msg = handle.next()?.context("Message not some")?;
+            let short_name: String = msg.read_key("shortName")?;
+            assert!(!short_name.is_empty());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn file_handles_dropped_in_loop() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+
+        // stream owns a duplicated descriptor, so it must be closed on drop
+        // and not leak descriptors above the process limit
+        for _ in 0..5000 {
+            let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+            assert!(handle.next()?.is_some());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn slice_constructor() -> Result<()> {
+        use crate::KeyRead;
+
+        let product_kind = ProductKind::GRIB;
+
+        let mut f = File::open(Path::new("./data/iceland.grib"))?;
+        let mut buf = Vec::new();
+        f.read_to_end(&mut buf)?;
+
+        let mut handle = CodesHandle::new_from_slice(&buf, product_kind)?;
+        assert!(!handle.source.pointer.is_null());
+
+        let msg = handle.next()?.context("Message not some")?;
+        let short_name: String = msg.read_key("shortName")?;
+        assert_eq!(short_name, "msl");
+
+        drop(handle);
+
+        // buffer is still owned by the caller
+        assert!(!buf.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn gzip_constructor() -> Result<()> {
+        use crate::KeyRead;
+        use flate2::{write::GzEncoder, Compression};
+        use std::{fs::remove_file, io::Write};
+
+        let gz_path = Path::new("./data/iceland-gzip-test.grib.gz");
+        let raw = std::fs::read("./data/iceland.grib")?;
+
+        let mut encoder = GzEncoder::new(File::create(gz_path)?, Compression::default());
+        encoder.write_all(&raw)?;
+        encoder.finish()?;
+
+        let mut handle = CodesHandle::new_from_gzip_file(gz_path, ProductKind::GRIB)?;
+        assert_eq!(handle.source.data(), &raw);
+
+        let msg = handle.next()?.context("no message")?;
+        let short_name: String = msg.read_key("shortName")?;
+        assert_eq!(short_name, "msl");
+
+        drop(handle);
+        remove_file(gz_path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn gzip_constructor_not_gzipped() {
+        use crate::CodesError;
+
+        let result = CodesHandle::new_from_gzip_file("./data/iceland.grib", ProductKind::GRIB);
+
+        assert!(matches!(result, Err(CodesError::NotGzipped)));
+    }
+
+    #[test]
+    #[cfg(feature = "memmap2")]
+    fn mmap_constructor() -> Result<()> {
+        let file_path = Path::new("./data/iceland-levels.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_mmap(file_path, product_kind)?;
+        assert!(!handle.source.pointer.is_null());
+        assert!(handle.current_message.is_none());
+        assert_eq!(
+            handle.source.data().len() as u64,
+            file_path.metadata()?.len()
+        );
+
+        let mut mmap_count = 0;
+        while handle.next()?.is_some() {
+            mmap_count += 1;
+        }
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut file_count = 0;
+        while handle.next()?.is_some() {
+            file_count += 1;
+        }
+
+        assert!(mmap_count > 0);
+        assert_eq!(mmap_count, file_count);
 
         Ok(())
     }
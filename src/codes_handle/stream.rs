@@ -0,0 +1,92 @@
+use crate::{
+    codes_handle::{CodesHandleIntoIter, HandleGenerator},
+    errors::CodesError,
+    CodesHandle, FallibleIterator, IntoFallibleIterator, KeyedMessage,
+};
+use futures_core::Stream;
+use std::{
+    fmt::Debug,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// [`Stream`] of messages of [`CodesHandle`], created with
+/// [`into_message_stream()`](CodesHandle::into_message_stream).
+///
+/// Each message is cloned out of the handle, in the same way as in [`CodesHandleIntoIter`].
+///
+/// Reading messages is done with blocking IO in ecCodes, so each poll blocks
+/// the current thread until the next message is read.
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+pub struct MessageStream<S: HandleGenerator + Debug> {
+    iter: CodesHandleIntoIter<S>,
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "futures")))]
+impl<S: HandleGenerator + Debug> CodesHandle<S> {
+    /// Converts the handle into a [`Stream`] yielding cloned messages,
+    /// for use with async code.
+    ///
+    /// ecCodes does not provide asynchronous IO, so the stream is always ready and polling it
+    /// reads the next message synchronously. For large files consider consuming the stream
+    /// inside [`spawn_blocking`](https://docs.rs/tokio/latest/tokio/task/fn.spawn_blocking.html)
+    /// or similar, to avoid blocking the async runtime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eccodes::{CodesHandle, ProductKind};
+    /// use futures::StreamExt;
+    /// # use std::path::Path;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # futures::executor::block_on(async {
+    /// let file_path = Path::new("./data/iceland-surface.grib");
+    /// let handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+    ///
+    /// let messages: Vec<_> = handle.into_message_stream().collect().await;
+    /// assert_eq!(messages.len(), 5);
+    /// # Ok(())
+    /// # })
+    /// # }
+    /// ```
+    pub fn into_message_stream(self) -> MessageStream<S> {
+        MessageStream {
+            iter: self.into_fallible_iter(),
+        }
+    }
+}
+
+impl<S: HandleGenerator + Debug + Unpin> Stream for MessageStream<S> {
+    type Item = Result<KeyedMessage, CodesError>;
+
+    fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.get_mut().iter.next().transpose())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CodesHandle, KeyRead, ProductKind};
+    use anyhow::Result;
+    use futures::StreamExt;
+    use std::path::Path;
+
+    #[tokio::test]
+    async fn message_stream_collected() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let product_kind = ProductKind::GRIB;
+        let handle = CodesHandle::new_from_file(file_path, product_kind)?;
+
+        let messages: Vec<_> = handle.into_message_stream().collect().await;
+
+        assert_eq!(messages.len(), 5);
+
+        for msg in messages {
+            let short_name: String = msg?.read_key("shortName")?;
+            assert!(!short_name.is_empty());
+        }
+
+        Ok(())
+    }
+}
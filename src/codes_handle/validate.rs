@@ -0,0 +1,140 @@
+use crate::{
+    codes_handle::CodesFile,
+    errors::{CodesError, CodesInternal},
+    CodesHandle, KeyRead,
+};
+use fallible_streaming_iterator::FallibleStreamingIterator;
+use std::fmt::Debug;
+
+/// Summary of the structural check returned by [`CodesHandle::validate()`].
+///
+/// Messages are indexed from 0 in the order they appear in the source.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ValidationReport {
+    /// Number of messages encountered, including ones that failed to decode
+    pub total: usize,
+    /// Number of messages that decoded correctly
+    pub ok: usize,
+    /// Index and error code of each message that failed to decode
+    pub errors: Vec<(usize, CodesInternal)>,
+}
+
+impl ValidationReport {
+    /// Returns `true` when no errors have been encountered.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl<D: Debug> CodesHandle<CodesFile<D>> {
+    /// Checks that all remaining messages in the file can be decoded
+    /// and returns a report instead of stopping on the first ecCodes error.
+    ///
+    /// Each message is decoded by reading its `values`. A message whose values cannot
+    /// be decoded is recorded in the report and the check continues with the next message.
+    /// When the message itself cannot be read from the file (eg. it is truncated),
+    /// the position in the file is no longer reliable, so the error is recorded and the check stops.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use eccodes::{CodesHandle, ProductKind};
+    /// # use std::path::Path;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland-surface.grib");
+    /// let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+    ///
+    /// let report = handle.validate()?;
+    /// assert!(report.is_valid());
+    /// assert_eq!(report.ok, 5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] only for errors not originating in ecCodes decoding,
+    /// eg. [`CodesError::NullPtr`]. Errors returned by ecCodes are recorded in the report.
+    pub fn validate(&mut self) -> Result<ValidationReport, CodesError> {
+        let mut report = ValidationReport::default();
+
+        loop {
+            if let Err(err) = self.advance() {
+                report.errors.push((report.total, internal_code(err)?));
+                report.total += 1;
+                break;
+            }
+
+            let Some(msg) = self.get() else {
+                break;
+            };
+
+            let decoded: Result<Vec<f64>, CodesError> = msg.read_key("values");
+
+            match decoded {
+                Ok(_) => report.ok += 1,
+                Err(err) => report.errors.push((report.total, internal_code(err)?)),
+            }
+
+            report.total += 1;
+        }
+
+        Ok(report)
+    }
+}
+
+/// Extracts ecCodes error code from the error, returning other errors back.
+fn internal_code(err: CodesError) -> Result<CodesInternal, CodesError> {
+    match err {
+        CodesError::Internal(code) => Ok(code),
+        CodesError::FunctionalityNotEnabled { .. } => {
+            Ok(CodesInternal::CodesFunctionalityNotEnabled)
+        }
+        err => Err(err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        codes_handle::{CodesHandle, ProductKind},
+        FallibleStreamingIterator,
+    };
+    use anyhow::{Context, Result};
+    use std::{fs::read, path::Path};
+
+    #[test]
+    fn validate_clean_file() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+
+        let report = handle.validate()?;
+
+        assert!(report.is_valid());
+        assert_eq!(report.total, 5);
+        assert_eq!(report.ok, 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn validate_truncated_file() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let first_size = handle.next()?.context("Message not some")?.encoded_size()?;
+
+        // first message is complete, second one is cut in half
+        let file_data = read(file_path)?;
+        let truncated = file_data[..first_size + first_size / 2].to_vec();
+
+        let mut handle = CodesHandle::new_from_memory(truncated, ProductKind::GRIB)?;
+        let report = handle.validate()?;
+
+        assert!(!report.is_valid());
+        assert_eq!(report.ok, 1);
+        assert_eq!(report.errors[0].0, 1);
+
+        Ok(())
+    }
+}
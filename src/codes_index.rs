@@ -34,17 +34,18 @@
 
 use crate::{
     codes_handle::HandleGenerator,
-    errors::CodesError,
+    errors::{CodesError, CodesInternal},
     intermediate_bindings::{
-        codes_handle_new_from_index, codes_index_add_file, codes_index_delete, codes_index_new,
+        codes_handle_delete, codes_handle_new_all_from_index, codes_handle_new_from_index,
+        codes_index_add_file, codes_index_delete, codes_index_new, codes_index_new_from_file,
         codes_index_read, codes_index_select_double, codes_index_select_long,
-        codes_index_select_string,
+        codes_index_select_string, codes_index_write,
     },
+    KeyedMessage,
 };
 use eccodes_sys::{codes_handle, codes_index};
-use std::{path::Path, ptr::null_mut};
+use std::{fmt::Display, path::Path, ptr::null_mut, time::Duration};
 
-#[derive(Debug)]
 #[cfg_attr(docsrs, doc(cfg(feature = "experimental_index")))]
 /// Structure representing an index of messages inside a GRIB file
 ///
@@ -85,6 +86,18 @@ use std::{path::Path, ptr::null_mut};
 /// ```
 pub struct CodesIndex {
     pub(crate) pointer: *mut codes_index,
+    retries: usize,
+    lock_timeout: Option<Duration>,
+}
+
+/// Prints the settings of the index without the raw pointer.
+impl std::fmt::Debug for CodesIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CodesIndex")
+            .field("retries", &self.retries)
+            .field("lock_timeout", &self.lock_timeout)
+            .finish_non_exhaustive()
+    }
 }
 
 /// Selection of messages from the [`CodesIndex`] by key-value pairs. [`CodesHandle`](crate::codes_handle::CodesHandle)
@@ -146,6 +159,54 @@ impl CodesIndex {
         }
         Ok(CodesIndex {
             pointer: index_handle,
+            retries: 0,
+            lock_timeout: None,
+        })
+    }
+
+    /// Constructs a new `CodesIndex` from keys and a GRIB file at given path.
+    ///
+    /// This is equivalent to calling [`new_from_keys`](CodesIndex::new_from_keys) and
+    /// [`add_grib_file`](CodesIndex::add_grib_file), but performed by ecCodes in a single call.
+    /// More files can still be added to the index with [`add_grib_file`](CodesIndex::add_grib_file).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// # use eccodes::codes_index::{CodesIndex, Select};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let grib_path = Path::new("./data/iceland.grib");
+    /// let index = CodesIndex::new_from_file(grib_path, &["shortName", "typeOfLevel"])?
+    ///     .select("shortName", "2t")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`CodesError::Internal`] if the index cannot be created
+    /// or the GRIB file cannot be indexed.
+    #[cfg_attr(docsrs, doc(cfg(feature = "experimental_index")))]
+    pub fn new_from_file<P: AsRef<Path>>(
+        grib_file_path: P,
+        keys: &[&str],
+    ) -> Result<CodesIndex, CodesError> {
+        let grib_file_path: &Path = grib_file_path.as_ref();
+        let file_path = grib_file_path
+            .to_str()
+            .ok_or_else(|| CodesError::InvalidUtf8Path(grib_file_path.to_path_buf()))?;
+        let keys = keys.join(",");
+
+        let index_handle;
+        unsafe {
+            index_handle = codes_index_new_from_file(file_path, &keys)?;
+        }
+
+        Ok(CodesIndex {
+            pointer: index_handle,
+            retries: 0,
+            lock_timeout: None,
         })
     }
 
@@ -174,9 +235,9 @@ impl CodesIndex {
     #[cfg_attr(docsrs, doc(cfg(feature = "experimental_index")))]
     pub fn read_from_file<P: AsRef<Path>>(index_file_path: P) -> Result<CodesIndex, CodesError> {
         let index_file_path: &Path = index_file_path.as_ref();
-        let file_path = index_file_path.to_str().ok_or_else(|| {
-            std::io::Error::new(std::io::ErrorKind::InvalidData, "Path is not valid utf8")
-        })?;
+        let file_path = index_file_path
+            .to_str()
+            .ok_or_else(|| CodesError::InvalidUtf8Path(index_file_path.to_path_buf()))?;
 
         let index_handle;
         unsafe {
@@ -185,6 +246,8 @@ impl CodesIndex {
 
         Ok(CodesIndex {
             pointer: index_handle,
+            retries: 0,
+            lock_timeout: None,
         })
     }
 
@@ -219,26 +282,291 @@ impl CodesIndex {
         index_file_path: P,
     ) -> Result<CodesIndex, CodesError> {
         let index_file_path: &Path = index_file_path.as_ref();
-        let file_path = index_file_path.to_str().ok_or_else(|| {
-            std::io::Error::new(std::io::ErrorKind::InvalidData, "Path is not valid utf8")
-        })?;
+        let file_path = index_file_path
+            .to_str()
+            .ok_or_else(|| CodesError::InvalidUtf8Path(index_file_path.to_path_buf()))?;
 
         let new_index = self;
 
-        unsafe {
-            codes_index_add_file(new_index.pointer, file_path)?;
-        }
+        retry_transient(new_index.retries, || unsafe {
+            codes_index_add_file(new_index.pointer, file_path, new_index.lock_timeout)
+        })?;
 
         Ok(new_index)
     }
+
+    /// Adds multiple GRIB files to the index, eg. all files of a dataset.
+    ///
+    /// Files are added in the given order with [`add_grib_file`](CodesIndex::add_grib_file),
+    /// each under the global ecCodes mutex. Adding stops at the first file that fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// # use eccodes::codes_index::CodesIndex;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let keys = vec!["shortName", "typeOfLevel", "level"];
+    /// let index = CodesIndex::new_from_keys(&keys)?.add_grib_files(&[
+    ///     Path::new("./data/iceland-surface.grib"),
+    ///     Path::new("./data/iceland-levels.grib"),
+    /// ])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::IndexAddFile`] with the path of the file that failed
+    /// and the error returned by [`add_grib_file`](CodesIndex::add_grib_file).
+    #[cfg_attr(docsrs, doc(cfg(feature = "experimental_index")))]
+    pub fn add_grib_files<P: AsRef<Path>>(self, paths: &[P]) -> Result<CodesIndex, CodesError> {
+        paths.iter().try_fold(self, |index, path| {
+            index
+                .add_grib_file(path)
+                .map_err(|source| CodesError::IndexAddFile {
+                    path: path.as_ref().to_path_buf(),
+                    source: Box::new(source),
+                })
+        })
+    }
+
+    /// Same as [`select`](Select::select), but returns [`CodesError::NoMatchingMessages`]
+    /// when no message matches the selection, instead of silently yielding no messages.
+    ///
+    /// Matching is checked by reading the first selected message, with all previous selections applied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// # use eccodes::codes_index::{CodesIndex, Select};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let index = CodesIndex::new_from_file(Path::new("./data/iceland.grib"), &["shortName", "typeOfLevel"])?
+    ///     .select_checked("shortName", "msl")?
+    ///     .select_checked("typeOfLevel", "surface")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::NoMatchingMessages`] when no message matches the selection.
+    ///
+    /// Returns [`CodesError::Internal`] with error code when the selection fails
+    /// or the message cannot be read.
+    #[cfg_attr(docsrs, doc(cfg(feature = "experimental_index")))]
+    pub fn select_checked<T>(self, key: &str, value: T) -> Result<CodesIndex, CodesError>
+    where
+        CodesIndex: Select<T>,
+        T: Copy + Display,
+    {
+        let index = self.select(key, value)?;
+
+        let first_handle = unsafe { codes_handle_new_from_index(index.pointer)? };
+
+        if first_handle.is_null() {
+            return Err(CodesError::NoMatchingMessages {
+                key: key.to_owned(),
+                value: value.to_string(),
+            });
+        }
+
+        unsafe { codes_handle_delete(first_handle)? };
+
+        // selecting again rewinds the index, so the probed message is not skipped
+        index.select(key, value)
+    }
+
+    /// Writes the index to a file at given path, so it can be later loaded
+    /// with [`read_from_file`](CodesIndex::read_from_file).
+    ///
+    /// Index file stores paths of indexed GRIB files as they were given when adding them,
+    /// so these files must be accessible with the same paths when the index is read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// # use eccodes::codes_index::CodesIndex;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let index = CodesIndex::new_from_file(Path::new("./data/iceland.grib"), &["shortName", "level"])?;
+    /// index.write_to_file(Path::new("./data/iceland_doc.grib.idx"))?;
+    /// # std::fs::remove_file(Path::new("./data/iceland_doc.grib.idx"))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function will return [`CodesError::Internal`] if the index file cannot be written.
+    #[cfg_attr(docsrs, doc(cfg(feature = "experimental_index")))]
+    pub fn write_to_file<P: AsRef<Path>>(&self, index_file_path: P) -> Result<(), CodesError> {
+        let index_file_path: &Path = index_file_path.as_ref();
+        let file_path = index_file_path
+            .to_str()
+            .ok_or_else(|| CodesError::InvalidUtf8Path(index_file_path.to_path_buf()))?;
+
+        retry_transient(self.retries, || unsafe {
+            codes_index_write(self.pointer, file_path)
+        })
+    }
+
+    /// Sets the number of times [`select`](Select::select) and [`add_grib_file`](CodesIndex::add_grib_file)
+    /// are retried when they fail with [`CodesIoProblem`](CodesInternal::CodesIoProblem).
+    ///
+    /// Such errors sporadically appear when indexes are used concurrently (see [module-level](crate::codes_index) documentation).
+    /// The global mutex is released and reacquired between attempts. By default operations are not retried.
+    /// Errors other than `CodesIoProblem` and problems persisting after all retries are returned as usual.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// # use eccodes::codes_index::{CodesIndex, Select};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let index = CodesIndex::new_from_keys(&["shortName", "typeOfLevel"])?
+    ///     .with_retries(3)
+    ///     .add_grib_file(Path::new("./data/iceland.grib"))?
+    ///     .select("shortName", "2t")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    #[cfg_attr(docsrs, doc(cfg(feature = "experimental_index")))]
+    pub fn with_retries(self, retries: usize) -> CodesIndex {
+        let mut new_index = self;
+        new_index.retries = retries;
+        new_index
+    }
+
+    /// Sets the maximum time [`select`](Select::select) and [`add_grib_file`](CodesIndex::add_grib_file)
+    /// wait for the global mutex guarding `codes_index` functions.
+    ///
+    /// By default operations wait for the mutex indefinitely, which can hang the program
+    /// when another thread gets stuck in pathological concurrent use (see [module-level](crate::codes_index) documentation).
+    /// With the timeout set, such operations return [`CodesError::MutexTimeout`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::{path::Path, time::Duration};
+    /// # use eccodes::codes_index::{CodesIndex, Select};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let index = CodesIndex::new_from_keys(&["shortName", "typeOfLevel"])?
+    ///     .with_lock_timeout(Duration::from_secs(5))
+    ///     .add_grib_file(Path::new("./data/iceland.grib"))?
+    ///     .select("shortName", "2t")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    #[cfg_attr(docsrs, doc(cfg(feature = "experimental_index")))]
+    pub fn with_lock_timeout(self, timeout: Duration) -> CodesIndex {
+        let mut new_index = self;
+        new_index.lock_timeout = Some(timeout);
+        new_index
+    }
+
+    /// Collects all messages matching the current selection into a vector, consuming the index.
+    ///
+    /// Unlike iterating over [`CodesHandle::new_from_index()`](crate::CodesHandle::new_from_index),
+    /// the global index mutex is held for the whole operation, so no other `codes_index` function
+    /// can interleave between reading consecutive messages.
+    /// Each returned [`KeyedMessage`] is a copy of the message read from the index,
+    /// so it does not depend on the index and can be used after the index is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::path::Path;
+    /// # use eccodes::codes_index::{CodesIndex, Select};
+    /// # fn main() -> anyhow::Result<()> {
+    /// let messages = CodesIndex::new_from_keys(&["shortName", "typeOfLevel"])?
+    ///     .add_grib_file(Path::new("./data/iceland.grib"))?
+    ///     .select("shortName", "msl")?
+    ///     .select("typeOfLevel", "surface")?
+    ///     .into_messages()?;
+    ///
+    /// assert!(!messages.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::Internal`] with error code
+    /// when internal [`codes_handle`] cannot be created from the index.
+    ///
+    /// Returns [`CodesError::CloneFailed`] when ecCodes fails to copy a message read from the index.
+    #[cfg_attr(docsrs, doc(cfg(feature = "experimental_index")))]
+    pub fn into_messages(self) -> Result<Vec<KeyedMessage>, CodesError> {
+        let handles = unsafe { codes_handle_new_all_from_index(self.pointer)? };
+
+        // handles created from the index may reference its data, so they are cloned
+        // and the originals are deleted (when dropped) before the index itself
+        let index_messages: Vec<KeyedMessage> = handles
+            .into_iter()
+            .map(|message_handle| KeyedMessage {
+                message_handle,
+                keys_namespace: None,
+            })
+            .collect();
+
+        index_messages.iter().map(KeyedMessage::try_clone).collect()
+    }
+}
+
+/// Indexes the GRIB file by given keys and writes the index to `out_idx_path`,
+/// so it can be later loaded with [`CodesIndex::read_from_file()`].
+///
+/// # Example
+///
+/// ```
+/// # use std::path::Path;
+/// # fn main() -> anyhow::Result<()> {
+/// let grib_path = Path::new("./data/iceland-levels.grib");
+/// let idx_path = Path::new("./data/iceland-levels-doc.grib.idx");
+///
+/// eccodes::build_index(grib_path, &["shortName", "typeOfLevel", "level"], idx_path)?;
+/// # std::fs::remove_file(idx_path)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// This function will return [`CodesError::Internal`] if the GRIB file cannot be indexed
+/// or the index file cannot be written.
+#[cfg_attr(docsrs, doc(cfg(feature = "experimental_index")))]
+pub fn build_index(grib_path: &Path, keys: &[&str], out_idx_path: &Path) -> Result<(), CodesError> {
+    CodesIndex::new_from_file(grib_path, keys)?.write_to_file(out_idx_path)
+}
+
+/// Calls `operation` until it succeeds, fails with an error other than `CodesIoProblem`
+/// or is retried `retries` times.
+fn retry_transient<F>(retries: usize, mut operation: F) -> Result<(), CodesError>
+where
+    F: FnMut() -> Result<(), CodesError>,
+{
+    let mut attempt = 0;
+
+    loop {
+        match operation() {
+            Err(CodesError::Internal(CodesInternal::CodesIoProblem)) if attempt < retries => {
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
 }
 
 impl Select<i64> for CodesIndex {
     fn select(self, key: &str, value: i64) -> Result<CodesIndex, CodesError> {
         let new_index = self;
-        unsafe {
-            codes_index_select_long(new_index.pointer, key, value)?;
-        }
+        retry_transient(new_index.retries, || unsafe {
+            codes_index_select_long(new_index.pointer, key, value, new_index.lock_timeout)
+        })?;
 
         Ok(new_index)
     }
@@ -246,18 +574,18 @@ impl Select<i64> for CodesIndex {
 impl Select<f64> for CodesIndex {
     fn select(self, key: &str, value: f64) -> Result<CodesIndex, CodesError> {
         let new_index = self;
-        unsafe {
-            codes_index_select_double(new_index.pointer, key, value)?;
-        }
+        retry_transient(new_index.retries, || unsafe {
+            codes_index_select_double(new_index.pointer, key, value, new_index.lock_timeout)
+        })?;
         Ok(new_index)
     }
 }
 impl Select<&str> for CodesIndex {
     fn select(self, key: &str, value: &str) -> Result<CodesIndex, CodesError> {
         let new_index = self;
-        unsafe {
-            codes_index_select_string(new_index.pointer, key, value)?;
-        }
+        retry_transient(new_index.retries, || unsafe {
+            codes_index_select_string(new_index.pointer, key, value, new_index.lock_timeout)
+        })?;
         Ok(new_index)
     }
 }
@@ -282,11 +610,12 @@ mod tests {
     use fallible_streaming_iterator::FallibleStreamingIterator;
 
     use crate::{
-        codes_index::{CodesIndex, Select},
+        codes_index::{build_index, retry_transient, CodesIndex, Select},
         errors::CodesInternal,
-        CodesError, CodesHandle,
+        CodesError, CodesHandle, KeyRead,
     };
-    use std::path::Path;
+    use eccodes_sys::CODES_LOCK;
+    use std::{fs::remove_file, path::Path, sync::mpsc, thread, time::Duration};
     #[test]
     fn index_constructors() -> Result<()> {
         {
@@ -330,6 +659,52 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn add_multiple_files() -> Result<()> {
+        let keys = vec!["shortName", "typeOfLevel", "level"];
+        let paths = [
+            Path::new("./data/iceland-surface.grib"),
+            Path::new("./data/iceland-levels.grib"),
+        ];
+
+        let surface = CodesIndex::new_from_keys(&keys)?
+            .add_grib_files(&paths)?
+            .select("typeOfLevel", "surface")?
+            .into_messages()?;
+        let levels = CodesIndex::new_from_keys(&keys)?
+            .add_grib_files(&paths)?
+            .select("typeOfLevel", "isobaricInhPa")?
+            .into_messages()?;
+
+        assert!(!surface.is_empty());
+        assert!(!levels.is_empty());
+
+        let missing_path = Path::new("./data/does-not-exist.grib");
+        let result = CodesIndex::new_from_keys(&keys)?.add_grib_files(&[paths[0], missing_path]);
+
+        match result {
+            Err(CodesError::IndexAddFile { path, .. }) => assert_eq!(path, missing_path),
+            _ => bail!("Expected IndexAddFile error"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn index_from_grib_file() -> Result<()> {
+        let grib_path = Path::new("./data/iceland.grib");
+        let index = CodesIndex::new_from_file(grib_path, &["shortName", "typeOfLevel"])?
+            .select("shortName", "msl")?
+            .select("typeOfLevel", "surface")?;
+
+        let mut handle = CodesHandle::new_from_index(index)?;
+        let msg = handle.next()?.context("no message")?;
+        let short_name: String = msg.read_key("shortName")?;
+
+        assert_eq!(short_name, "msl");
+        Ok(())
+    }
+
     #[test]
     fn index_selection() -> Result<()> {
         let file_path = Path::new("./data/iceland-surface.grib.idx");
@@ -343,6 +718,61 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn retry_transient_failure() -> Result<()> {
+        let mut attempts = 0;
+
+        retry_transient(2, || {
+            attempts += 1;
+            if attempts == 1 {
+                Err(CodesInternal::CodesIoProblem.into())
+            } else {
+                Ok(())
+            }
+        })?;
+
+        assert_eq!(attempts, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn retry_persistent_failure() {
+        let mut attempts = 0;
+
+        let result = retry_transient(2, || {
+            attempts += 1;
+            Err(CodesInternal::CodesIoProblem.into())
+        });
+
+        assert!(matches!(
+            result,
+            Err(CodesError::Internal(CodesInternal::CodesIoProblem))
+        ));
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        let result = retry_transient(2, || {
+            attempts += 1;
+            Err(CodesInternal::CodesNotFound.into())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn selection_with_retries() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib.idx");
+        let index = CodesIndex::read_from_file(file_path)?
+            .with_retries(3)
+            .select("shortName", "2t")?
+            .select("level", 0)?;
+
+        assert_eq!(index.retries, 3);
+        assert!(!index.pointer.is_null());
+        Ok(())
+    }
+
     #[test]
     fn incorrect_index_path() -> Result<()> {
         let file_path = Path::new("./data/iceland-levels-bad-path.grib.idx");
@@ -391,4 +821,142 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn index_into_messages() -> Result<()> {
+        let keys = ["typeOfLevel", "level"];
+        let grib_path = Path::new("./data/iceland-levels.grib");
+        let messages = CodesIndex::new_from_keys(&keys)?
+            .add_grib_file(grib_path)?
+            .select("typeOfLevel", "isobaricInhPa")?
+            .select("level", 600)?
+            .into_messages()?;
+
+        assert!(!messages.is_empty());
+
+        for msg in &messages {
+            let level: i64 = msg.read_key("level")?;
+            assert_eq!(level, 600);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn index_into_messages_outlive_index() -> Result<()> {
+        let keys = ["shortName", "typeOfLevel"];
+        let grib_path = Path::new("./data/iceland-surface.grib");
+        let index = CodesIndex::new_from_keys(&keys)?
+            .add_grib_file(grib_path)?
+            .select("shortName", "2t")?
+            .select("typeOfLevel", "surface")?;
+
+        // the index is dropped inside into_messages, before any key is read
+        let messages = index.into_messages()?;
+        assert_eq!(messages.len(), 1);
+
+        let short_name: String = messages[0].read_key("shortName")?;
+        assert_eq!(short_name, "2t");
+
+        let values: Vec<f64> = messages[0].read_key("values")?;
+        assert!(!values.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn selection_lock_timeout() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib.idx");
+        let index =
+            CodesIndex::read_from_file(file_path)?.with_lock_timeout(Duration::from_millis(50));
+
+        let (locked_tx, locked_rx) = mpsc::channel();
+        let holder = thread::spawn(move || {
+            let _g = CODES_LOCK.lock().unwrap();
+            locked_tx.send(()).unwrap();
+            thread::sleep(Duration::from_millis(500));
+        });
+
+        locked_rx.recv()?;
+        let result = index.select("shortName", "2t");
+        holder.join().unwrap();
+
+        assert!(matches!(result, Err(CodesError::MutexTimeout(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn build_index_sidecar() -> Result<()> {
+        let grib_path = Path::new("./data/iceland-levels.grib");
+        let idx_path = Path::new("./data/iceland-levels-build.grib.idx");
+
+        build_index(grib_path, &["shortName", "typeOfLevel", "level"], idx_path)?;
+
+        let index = CodesIndex::read_from_file(idx_path)?
+            .select("typeOfLevel", "isobaricInhPa")?
+            .select("level", 700)?;
+        remove_file(idx_path)?;
+
+        let mut handle = CodesHandle::new_from_index(index)?;
+        let msg = handle.next()?.context("no message")?;
+        let level: i64 = msg.read_key("level")?;
+
+        assert_eq!(level, 700);
+        Ok(())
+    }
+
+    #[test]
+    fn select_checked_no_match() -> Result<()> {
+        let keys = ["typeOfLevel", "level"];
+        let grib_path = Path::new("./data/iceland-levels.grib");
+
+        let result = CodesIndex::new_from_file(grib_path, &keys)?
+            .select_checked("typeOfLevel", "isobaricInhPa")?
+            .select_checked("level", 1234);
+
+        match result {
+            Err(CodesError::NoMatchingMessages { key, value }) => {
+                assert_eq!(key, "level");
+                assert_eq!(value, "1234");
+            }
+            _ => bail!("Expected NoMatchingMessages error"),
+        }
+
+        let checked = CodesIndex::new_from_file(grib_path, &keys)?
+            .select_checked("typeOfLevel", "isobaricInhPa")?
+            .select_checked("level", 600)?
+            .into_messages()?;
+        let unchecked = CodesIndex::new_from_file(grib_path, &keys)?
+            .select("typeOfLevel", "isobaricInhPa")?
+            .select("level", 600)?
+            .into_messages()?;
+
+        // the probed message is still returned by the index
+        assert!(!checked.is_empty());
+        assert_eq!(checked.len(), unchecked.len());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn non_utf8_path() -> Result<()> {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let index_path = Path::new(OsStr::from_bytes(b"./data/invalid\xff.idx"));
+
+        match CodesIndex::read_from_file(index_path) {
+            Err(CodesError::InvalidUtf8Path(path)) => assert_eq!(path, index_path),
+            _ => bail!("Expected InvalidUtf8Path error"),
+        }
+
+        let index =
+            CodesIndex::new_from_file(Path::new("./data/iceland-surface.grib"), &["shortName"])?;
+        assert!(matches!(
+            index.add_grib_file(index_path),
+            Err(CodesError::InvalidUtf8Path(_))
+        ));
+
+        Ok(())
+    }
 }
@@ -3,14 +3,14 @@
 
 use std::ptr::null_mut;
 
-use eccodes_sys::codes_nearest;
+use eccodes_sys::{codes_nearest, CODES_NEAREST_SAME_DATA, CODES_NEAREST_SAME_GRID};
 use log::error;
 
 use crate::{
     intermediate_bindings::{
         codes_grib_nearest_delete, codes_grib_nearest_find, codes_grib_nearest_new,
     },
-    CodesError, KeyedMessage,
+    CodesError, DynamicKeyType, KeyedMessage,
 };
 
 /// The structure used to find nearest gridpoints in `KeyedMessage`.
@@ -20,6 +20,21 @@ pub struct CodesNearest<'a> {
     parent_message: &'a KeyedMessage,
 }
 
+/// Reusable nearest gridpoint search for many messages defined on the same grid,
+/// eg. when extracting a time series of a station from many messages.
+///
+/// Creating the nearest structure for a message requires ecCodes to compute coordinates of the grid,
+/// which is expensive. [`CodesNearest`] does that again for each message, while this cache
+/// keeps the structure as long as consecutive messages have the same grid
+/// (compared with the same keys as in [`KeyedMessage::same_grid_as()`]) and rebuilds it only when the grid changes.
+///
+/// The cache does not borrow messages, so it can be kept across iterations of [`CodesHandle`](crate::CodesHandle).
+#[derive(Debug)]
+pub struct CodesNearestCache {
+    nearest_handle: *mut codes_nearest,
+    grid_signature: Vec<Option<DynamicKeyType>>,
+}
+
 /// The structure returned by [`CodesNearest::find_nearest()`].
 /// Should always be analysed in relation to the coordinates requested in `find_nearest()`.
 #[derive(Copy, Clone, PartialEq, Debug, Default)]
@@ -36,6 +51,20 @@ pub struct NearestGridpoint {
     pub value: f64,
 }
 
+/// Four nearest gridpoints returned by [`CodesNearest::find_quad()`],
+/// arranged by their position around the requested point.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct NeighbourQuad {
+    ///Northern gridpoint with lower longitude
+    pub north_west: NearestGridpoint,
+    ///Northern gridpoint with higher longitude
+    pub north_east: NearestGridpoint,
+    ///Southern gridpoint with lower longitude
+    pub south_west: NearestGridpoint,
+    ///Southern gridpoint with higher longitude
+    pub south_east: NearestGridpoint,
+}
+
 impl KeyedMessage {
     /// Creates a new instance of [`CodesNearest`] for the `KeyedMessage`.
     /// [`CodesNearest`] can be used to find nearest gridpoints for given coordinates in the `KeyedMessage`
@@ -61,6 +90,9 @@ impl CodesNearest<'_> {
     ///The inputs are latitude and longitude of requested point in respectively degrees north and
     ///degreed east.
     ///
+    ///The order of returned gridpoints is decided by ecCodes and should not be relied on.
+    ///Use [`find_quad()`](CodesNearest::find_quad) to get gridpoints arranged by their position.
+    ///
     ///### Example
     ///
     ///```
@@ -89,16 +121,260 @@ impl CodesNearest<'_> {
         let output_points;
 
         unsafe {
+            // such flags are set because find nearest for given nearest is always
+            // called on the same grib message
             output_points = codes_grib_nearest_find(
                 self.parent_message.message_handle,
                 self.nearest_handle,
                 lat,
                 lon,
+                CODES_NEAREST_SAME_GRID + CODES_NEAREST_SAME_DATA,
             )?;
         }
 
         Ok(output_points)
     }
+
+    ///Same as [`find_nearest()`](CodesNearest::find_nearest) but returns only the closest
+    ///of four nearest gridpoints for which `filter` returns `true`.
+    ///
+    ///Useful for example to select the nearest land (or sea) gridpoint
+    ///with a user-supplied lookup into a land-sea mask.
+    ///Returns `None` when none of the gridpoints passes the filter.
+    ///
+    ///### Example
+    ///
+    ///```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    /// # use std::path::Path;
+    /// use eccodes::FallibleStreamingIterator;
+    /// # use anyhow::Context;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland.grib");
+    /// let product_kind = ProductKind::GRIB;
+    ///
+    /// let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+    /// let msg = handle.next()?.context("no message")?;
+    ///
+    /// let c_nearest = msg.codes_nearest()?;
+    /// let out = c_nearest.find_nearest_filtered(64.13, -21.89, |point| point.lat >= 64.0)?;
+    /// # Ok(())
+    /// # }
+    ///```
+    ///
+    ///### Errors
+    ///
+    ///This function returns [`CodesInternal`](crate::errors::CodesInternal) when
+    ///one of ecCodes function returns the non-zero code.
+    pub fn find_nearest_filtered<F>(
+        &self,
+        lat: f64,
+        lon: f64,
+        filter: F,
+    ) -> Result<Option<NearestGridpoint>, CodesError>
+    where
+        F: Fn(&NearestGridpoint) -> bool,
+    {
+        let output_points = self.find_nearest(lat, lon)?;
+
+        Ok(output_points
+            .into_iter()
+            .filter(|point| filter(point))
+            .min_by(|a, b| a.distance.total_cmp(&b.distance)))
+    }
+
+    ///Same as [`find_nearest()`](CodesNearest::find_nearest) but returns each gridpoint together
+    ///with its normalized inverse-distance weight, which can be used for custom interpolation.
+    ///
+    ///Weights sum to 1 across the four points. When requested point lies exactly on a gridpoint,
+    ///that gridpoint has weight 1 and other gridpoints have weight 0.
+    ///
+    ///### Example
+    ///
+    ///```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    /// # use std::path::Path;
+    /// use eccodes::FallibleStreamingIterator;
+    /// # use anyhow::Context;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland.grib");
+    /// let product_kind = ProductKind::GRIB;
+    ///
+    /// let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+    /// let msg = handle.next()?.context("no message")?;
+    ///
+    /// let c_nearest = msg.codes_nearest()?;
+    /// let interpolated: f64 = c_nearest
+    ///     .find_nearest_weighted(64.13, -21.89)?
+    ///     .iter()
+    ///     .map(|(point, weight)| point.value * weight)
+    ///     .sum();
+    /// # Ok(())
+    /// # }
+    ///```
+    ///
+    ///### Errors
+    ///
+    ///This function returns [`CodesInternal`](crate::errors::CodesInternal) when
+    ///one of ecCodes function returns the non-zero code.
+    pub fn find_nearest_weighted(
+        &self,
+        lat: f64,
+        lon: f64,
+    ) -> Result<[(NearestGridpoint, f64); 4], CodesError> {
+        let output_points = self.find_nearest(lat, lon)?;
+
+        if let Some(hit) = output_points.iter().position(|point| point.distance == 0.0) {
+            return Ok(std::array::from_fn(|i| {
+                (output_points[i], if i == hit { 1.0 } else { 0.0 })
+            }));
+        }
+
+        let inverse_sum: f64 = output_points.iter().map(|point| 1.0 / point.distance).sum();
+
+        Ok(output_points.map(|point| (point, (1.0 / point.distance) / inverse_sum)))
+    }
+
+    ///Same as [`find_nearest()`](CodesNearest::find_nearest) but returns the four gridpoints
+    ///arranged into a [`NeighbourQuad`], which makes bilinear interpolation straightforward.
+    ///
+    ///Two gridpoints with the highest latitude are northern, and of each pair the gridpoint
+    ///with the lower longitude is western. Longitudes are compared relative to the requested
+    ///point, so gridpoints across the 0°/360° meridian are arranged correctly.
+    ///
+    ///### Example
+    ///
+    ///```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    /// # use std::path::Path;
+    /// use eccodes::FallibleStreamingIterator;
+    /// # use anyhow::Context;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland.grib");
+    /// let product_kind = ProductKind::GRIB;
+    ///
+    /// let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+    /// let msg = handle.next()?.context("no message")?;
+    ///
+    /// let c_nearest = msg.codes_nearest()?;
+    /// let quad = c_nearest.find_quad(64.13, -21.89)?;
+    /// assert!(quad.north_west.lat >= quad.south_west.lat);
+    /// # Ok(())
+    /// # }
+    ///```
+    ///
+    ///### Errors
+    ///
+    ///This function returns [`CodesInternal`](crate::errors::CodesInternal) when
+    ///one of ecCodes function returns the non-zero code.
+    pub fn find_quad(&self, lat: f64, lon: f64) -> Result<NeighbourQuad, CodesError> {
+        let mut points = self.find_nearest(lat, lon)?;
+
+        points.sort_by(|a, b| b.lat.total_cmp(&a.lat));
+
+        let lon_offset = |point: &NearestGridpoint| (point.lon - lon + 180.0).rem_euclid(360.0);
+        let (north, south) = points.split_at_mut(2);
+        north.sort_by(|a, b| lon_offset(a).total_cmp(&lon_offset(b)));
+        south.sort_by(|a, b| lon_offset(a).total_cmp(&lon_offset(b)));
+
+        Ok(NeighbourQuad {
+            north_west: points[0],
+            north_east: points[1],
+            south_west: points[2],
+            south_east: points[3],
+        })
+    }
+}
+
+impl CodesNearestCache {
+    /// Creates an empty cache. The nearest structure is built on the first call of
+    /// [`find_nearest()`](CodesNearestCache::find_nearest).
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            nearest_handle: null_mut(),
+            grid_signature: vec![],
+        }
+    }
+
+    ///Same as [`CodesNearest::find_nearest()`] but reuses the nearest structure
+    ///when the grid of `message` is the same as the grid of the previous message.
+    ///
+    ///### Example
+    ///
+    ///```
+    /// use eccodes::{CodesHandle, CodesNearestCache, FallibleStreamingIterator, ProductKind};
+    /// # use std::path::Path;
+    /// # fn main() -> anyhow::Result<()> {
+    /// let file_path = Path::new("./data/iceland-levels.grib");
+    /// let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+    ///
+    /// let mut cache = CodesNearestCache::new();
+    /// let mut profile = vec![];
+    ///
+    /// while let Some(msg) = handle.next()? {
+    ///     let nearest = cache.find_nearest(msg, 64.13, -21.89)?;
+    ///     profile.push(nearest[0].value);
+    /// }
+    /// # Ok(())
+    /// # }
+    ///```
+    ///
+    ///### Errors
+    ///
+    ///This function returns [`CodesInternal`](crate::errors::CodesInternal) when
+    ///one of ecCodes function returns the non-zero code or when grid keys cannot be read.
+    pub fn find_nearest(
+        &mut self,
+        message: &KeyedMessage,
+        lat: f64,
+        lon: f64,
+    ) -> Result<[NearestGridpoint; 4], CodesError> {
+        let grid_signature = message.grid_signature()?;
+
+        if self.nearest_handle.is_null() || grid_signature != self.grid_signature {
+            unsafe {
+                codes_grib_nearest_delete(self.nearest_handle)?;
+            }
+            self.nearest_handle = null_mut();
+
+            self.nearest_handle = unsafe { codes_grib_nearest_new(message.message_handle)? };
+            self.grid_signature = grid_signature;
+        }
+
+        // data values differ between messages, so only the grid is reused
+        unsafe {
+            codes_grib_nearest_find(
+                message.message_handle,
+                self.nearest_handle,
+                lat,
+                lon,
+                CODES_NEAREST_SAME_GRID,
+            )
+        }
+    }
+}
+
+impl Default for CodesNearestCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+impl Drop for CodesNearestCache {
+    fn drop(&mut self) {
+        unsafe {
+            codes_grib_nearest_delete(self.nearest_handle).unwrap_or_else(|error| {
+                error!(
+                    "codes_grib_nearest_delete() returned an error: {:?}",
+                    &error
+                );
+            });
+        }
+
+        self.nearest_handle = null_mut();
+    }
 }
 
 #[doc(hidden)]
@@ -124,6 +400,7 @@ mod tests {
     use anyhow::{Context, Result};
     use fallible_streaming_iterator::FallibleStreamingIterator;
 
+    use super::{CodesNearestCache, NearestGridpoint};
     use crate::{CodesHandle, ProductKind};
 
     #[test]
@@ -151,6 +428,120 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn find_nearest_filtered() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let msg = handle.next()?.context("Message not some")?;
+        let nrst = msg.codes_nearest()?;
+
+        let all = nrst.find_nearest(64.13, -21.89)?;
+        let threshold = all
+            .iter()
+            .map(|point| point.value)
+            .fold(f64::NEG_INFINITY, f64::max);
+
+        let expected = all
+            .iter()
+            .filter(|point| point.value < threshold)
+            .min_by(|a, b| a.distance.total_cmp(&b.distance))
+            .copied();
+
+        let filtered =
+            nrst.find_nearest_filtered(64.13, -21.89, |point| point.value < threshold)?;
+        assert_eq!(filtered, expected);
+        if let Some(point) = filtered {
+            assert!(point.value < threshold);
+        }
+
+        let none = nrst.find_nearest_filtered(64.13, -21.89, |_| false)?;
+        assert!(none.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_nearest_weighted() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let msg = handle.next()?.context("Message not some")?;
+        let nrst = msg.codes_nearest()?;
+
+        let weighted = nrst.find_nearest_weighted(64.13, -21.89)?;
+        let weight_sum: f64 = weighted.iter().map(|(_, weight)| weight).sum();
+        assert!((weight_sum - 1.0).abs() < 1e-9);
+
+        let closest = weighted
+            .iter()
+            .min_by(|a, b| a.0.distance.total_cmp(&b.0.distance))
+            .context("No gridpoints")?;
+        assert!(weighted.iter().all(|(_, weight)| *weight <= closest.1));
+
+        // gridpoint of the grid
+        let exact = nrst.find_nearest_weighted(64.0, -21.75)?;
+        let is_hit = |point: &NearestGridpoint| {
+            (point.lat - 64.0).abs() < 1e-6 && (point.lon - -21.75).abs() < 1e-6
+        };
+        assert_eq!(exact.iter().filter(|(point, _)| is_hit(point)).count(), 1);
+
+        for (point, weight) in &exact {
+            let expected = if is_hit(point) { 1.0 } else { 0.0 };
+            assert!((weight - expected).abs() < f64::EPSILON);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_quad() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let msg = handle.next()?.context("Message not some")?;
+        let nrst = msg.codes_nearest()?;
+
+        let all = nrst.find_nearest(64.13, -21.89)?;
+        let quad = nrst.find_quad(64.13, -21.89)?;
+
+        let max_lat = all.iter().map(|p| p.lat).fold(f64::NEG_INFINITY, f64::max);
+        let min_lat = all.iter().map(|p| p.lat).fold(f64::INFINITY, f64::min);
+        let max_lon = all.iter().map(|p| p.lon).fold(f64::NEG_INFINITY, f64::max);
+        let min_lon = all.iter().map(|p| p.lon).fold(f64::INFINITY, f64::min);
+
+        assert_eq!(
+            (quad.north_west.lat, quad.north_west.lon),
+            (max_lat, min_lon)
+        );
+        assert_eq!(
+            (quad.north_east.lat, quad.north_east.lon),
+            (max_lat, max_lon)
+        );
+        assert_eq!(
+            (quad.south_west.lat, quad.south_west.lon),
+            (min_lat, min_lon)
+        );
+        assert_eq!(
+            (quad.south_east.lat, quad.south_east.lon),
+            (min_lat, max_lon)
+        );
+
+        for point in [
+            quad.north_west,
+            quad.north_east,
+            quad.south_west,
+            quad.south_east,
+        ] {
+            assert!(all.contains(&point));
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn destructor() -> Result<()> {
         let file_path = Path::new("./data/iceland.grib");
@@ -179,4 +570,29 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn nearest_cache() -> Result<()> {
+        let file_path = Path::new("./data/iceland-levels.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut cache = CodesNearestCache::new();
+
+        while let Some(msg) = handle.next()? {
+            let expected = msg.codes_nearest()?.find_nearest(64.13, -21.89)?;
+            let cached = cache.find_nearest(msg, 64.13, -21.89)?;
+
+            assert_eq!(cached, expected);
+        }
+
+        let mut other_handle =
+            CodesHandle::new_from_file(Path::new("./data/gfs.grib"), product_kind)?;
+        let other_msg = other_handle.next()?.context("Message not some")?;
+
+        let expected = other_msg.codes_nearest()?.find_nearest(64.13, -21.89)?;
+        assert_eq!(cache.find_nearest(other_msg, 64.13, -21.89)?, expected);
+
+        Ok(())
+    }
 }
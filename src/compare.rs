@@ -0,0 +1,237 @@
+//! Definitions for comparing keys of `KeyedMessage`s and whole GRIB files,
+//! similar to `grib_compare` tool
+
+use std::{collections::HashSet, path::Path};
+
+use fallible_iterator::FallibleIterator;
+use fallible_streaming_iterator::FallibleStreamingIterator;
+
+use crate::{CodesError, CodesHandle, DynamicKeyType, KeyedMessage, ProductKind};
+
+/// Difference of a single key between two messages,
+/// returned by [`KeyedMessage::diff_keys()`].
+///
+/// Value is `None` when the key is not present in the message.
+#[derive(Clone, PartialEq, Debug)]
+pub struct KeyDiff {
+    /// Name of the key
+    pub key: String,
+    /// Value of the key in the first message
+    pub first: Option<DynamicKeyType>,
+    /// Value of the key in the second message
+    pub second: Option<DynamicKeyType>,
+}
+
+/// Difference between two GRIB files returned by [`compare_files()`].
+///
+/// Messages are paired by their position in files, `message_index` starts from 0.
+#[derive(Clone, PartialEq, Debug)]
+pub enum FileDiff {
+    /// Messages at the same position in both files have different keys
+    Keys {
+        /// Position of messages in files
+        message_index: usize,
+        /// Keys that differ between messages
+        diffs: Vec<KeyDiff>,
+    },
+    /// Message is present only in the first file
+    OnlyInFirst {
+        /// Position of the message in the first file
+        message_index: usize,
+    },
+    /// Message is present only in the second file
+    OnlyInSecond {
+        /// Position of the message in the second file
+        message_index: usize,
+    },
+}
+
+impl KeyedMessage {
+    /// Compares values of keys in this and the other message and returns keys that differ.
+    ///
+    /// When `keys` is `None` all keys present in any of the messages are compared,
+    /// in the order of [`default_keys_iterator()`](KeyedMessage::default_keys_iterator).
+    /// Keys present in only one of messages are reported as differences.
+    ///
+    /// Arrays (including `values`) are compared exactly, without any tolerance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland-surface.grib"), ProductKind::GRIB)?;
+    ///  let first = handle.next()?.context("no message")?.try_clone()?;
+    ///  let second = handle.next()?.context("no message")?;
+    ///
+    ///  let diffs = first.diff_keys(second, Some(&["shortName", "Ni"]))?;
+    ///  assert_eq!(diffs.len(), 1);
+    ///  assert_eq!(diffs[0].key, "shortName");
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when any of keys exists in the message but cannot be read
+    /// or when keys iterator cannot be created.
+    pub fn diff_keys(
+        &self,
+        other: &KeyedMessage,
+        keys: Option<&[&str]>,
+    ) -> Result<Vec<KeyDiff>, CodesError> {
+        let key_names: Vec<String> = if let Some(keys) = keys {
+            keys.iter().map(ToString::to_string).collect()
+        } else {
+            let mut seen = HashSet::new();
+            let mut key_names = vec![];
+
+            for message in [self, other] {
+                let mut kiter = message.default_keys_iterator()?;
+
+                while let Some(key_name) = kiter.next()? {
+                    if seen.insert(key_name.clone()) {
+                        key_names.push(key_name);
+                    }
+                }
+            }
+
+            key_names
+        };
+
+        let mut diffs = vec![];
+
+        for key in key_names {
+            let first = self.read_key_dynamic_if_present(&key)?;
+            let second = other.read_key_dynamic_if_present(&key)?;
+
+            if first != second {
+                diffs.push(KeyDiff { key, first, second });
+            }
+        }
+
+        Ok(diffs)
+    }
+}
+
+/// Compares two GRIB files message-by-message, similarly to `grib_compare` tool.
+///
+/// Messages are paired by their position in files and compared with [`KeyedMessage::diff_keys()`]
+/// using provided `keys` or all keys when `keys` is `None`. Only differing messages are reported,
+/// so identical files result in an empty vector.
+///
+/// # Example
+///
+/// ```
+/// # use std::path::Path;
+/// # fn main() -> anyhow::Result<()> {
+/// let file_path = Path::new("./data/iceland.grib");
+///
+/// let diffs = eccodes::compare_files(file_path, file_path, Some(&["shortName", "level"]))?;
+/// assert!(diffs.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`CodesError`] when any of the files cannot be opened or read,
+/// or when keys cannot be compared.
+pub fn compare_files(
+    first: &Path,
+    second: &Path,
+    keys: Option<&[&str]>,
+) -> Result<Vec<FileDiff>, CodesError> {
+    let mut first_handle = CodesHandle::new_from_file(first, ProductKind::GRIB)?;
+    let mut second_handle = CodesHandle::new_from_file(second, ProductKind::GRIB)?;
+
+    let mut file_diffs = vec![];
+    let mut message_index = 0;
+
+    loop {
+        first_handle.advance()?;
+        second_handle.advance()?;
+
+        match (first_handle.get(), second_handle.get()) {
+            (Some(first_msg), Some(second_msg)) => {
+                let diffs = first_msg.diff_keys(second_msg, keys)?;
+
+                if !diffs.is_empty() {
+                    file_diffs.push(FileDiff::Keys {
+                        message_index,
+                        diffs,
+                    });
+                }
+            }
+            (Some(_), None) => file_diffs.push(FileDiff::OnlyInFirst { message_index }),
+            (None, Some(_)) => file_diffs.push(FileDiff::OnlyInSecond { message_index }),
+            (None, None) => break,
+        }
+
+        message_index += 1;
+    }
+
+    Ok(file_diffs)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{Context, Result};
+    use fallible_streaming_iterator::FallibleStreamingIterator;
+    use std::{fs::remove_file, path::Path};
+
+    use super::{compare_files, FileDiff};
+    use crate::{CodesHandle, DynamicKeyType, KeyWrite, ProductKind};
+
+    #[test]
+    fn compare_same_file() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+
+        let diffs = compare_files(file_path, file_path, None)?;
+
+        assert!(diffs.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn compare_edited_file() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let edited_path = Path::new("./data/iceland_compare_edited.grib");
+
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut first = handle.next()?.context("Message not some")?.try_clone()?;
+        first.write_key("centre", "cnmc")?;
+        first.write_to_file(edited_path, false)?;
+
+        let diffs = compare_files(file_path, edited_path, Some(&["centre", "shortName"]))?;
+        remove_file(edited_path)?;
+
+        let FileDiff::Keys {
+            message_index,
+            diffs: key_diffs,
+        } = &diffs[0]
+        else {
+            panic!("Expected key differences in the first message");
+        };
+
+        assert_eq!(*message_index, 0);
+        assert_eq!(key_diffs.len(), 1);
+        assert_eq!(key_diffs[0].key, "centre");
+        assert_eq!(
+            key_diffs[0].second,
+            Some(DynamicKeyType::Str("cnmc".to_string()))
+        );
+
+        // the edited file contains only the first message
+        assert!(diffs[1..]
+            .iter()
+            .all(|diff| matches!(diff, FileDiff::OnlyInFirst { .. })));
+
+        Ok(())
+    }
+}
@@ -5,8 +5,9 @@
 //! If you encounter an error that you believe is a result of implementation bug
 //! rather then user mistake post an issue on Github.
 
+use crate::keyed_message::{GridType, NativeKeyType};
 use errno::Errno;
-use num_derive::FromPrimitive;
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// Errors returned by the all functions in the crate.
@@ -15,20 +16,58 @@ pub enum CodesError {
     ///Returned when ecCodes library function returns an error code.
     ///Check [`CodesInternal`] for more details.
     #[error("ecCodes function returned a non-zero code {0}")]
-    Internal(#[from] CodesInternal),
+    Internal(#[source] CodesInternal),
+
+    ///Returned when ecCodes function returns [`CodesInternal::CodesFunctionalityNotEnabled`],
+    ///which usually means that ecCodes has been built without support for a packing type
+    ///(eg. JPEG, PNG or CCSDS) used by the message. The hint suggests which build option to check.
+    #[error("ecCodes functionality not enabled: {hint}")]
+    FunctionalityNotEnabled {
+        /// Suggestion of the likely cause of the error
+        hint: &'static str,
+    },
 
     ///Returned when one of libc functions returns a non-zero error code.
     ///Check libc documentation for details of the errors.
     ///For libc reference check these websites: ([1](https://man7.org/linux/man-pages/index.html))
     ///([2](https://pubs.opengroup.org/onlinepubs/9699919799/functions/contents.html))
-    #[error("libc function returned an error with code {0} and errno {1}")]
-    LibcNonZero(i32, Errno),
+    ///The name of the failed function is included, as eg. `fdopen` and `fmemopen` failures have very different causes.
+    #[error("libc function {op} returned an error with code {code} and errno {errno}")]
+    LibcNonZero {
+        /// Error code returned by the function
+        code: i32,
+        /// Value of `errno` after the call
+        errno: Errno,
+        /// Name of the libc function that failed
+        op: &'static str,
+    },
+
+    ///Returned when the stream position does not fit in the `off_t` type,
+    ///so the stream cannot be moved to it with `fseeko`.
+    #[error("Stream position {0} is out of range of off_t")]
+    StreamPositionOutOfRange(u64),
 
     ///Returned when there is an issue while handlng the file.
     ///Check the [`std::fs`] documentation why and when this error can occur.
     #[error("Error occured while opening the file: {0}")]
     FileHandlingInterrupted(#[from] std::io::Error),
 
+    ///Returned when the path passed to ecCodes is not valid UTF-8,
+    ///as ecCodes functions accept only string paths.
+    #[error("Path {} is not valid UTF-8", .0.display())]
+    InvalidUtf8Path(PathBuf),
+
+    ///Returned when the file at given path cannot be opened.
+    ///Contains the path, so it is clear which file failed when handling many files.
+    #[error("Cannot open the file {}: {source}", path.display())]
+    FileOpen {
+        /// Path of the file that failed to open
+        path: PathBuf,
+        /// Underlying IO error
+        #[source]
+        source: std::io::Error,
+    },
+
     ///Returned when the string cannot be parsed as valid UTF8 string.
     #[error("Cannot parse string as UTF8: {0}")]
     CstrUTF8(#[from] std::str::Utf8Error),
@@ -48,13 +87,80 @@ pub enum CodesError {
     #[error("Incorrect key size")]
     IncorrectKeySize,
 
-    /// Returned when trying to read array as number.
+    /// Returned when trying to read array as number,
+    /// or when an array to write does not have the length required by the message.
     #[error("Requested key size is incorrect")]
     WrongRequestedKeySize,
 
     /// Returned when trying to checked read key in non-native type.
-    #[error("Requested key type is incorrect")]
-    WrongRequestedKeyType,
+    /// Contains the requested Rust type and the native type of the key.
+    #[error("Requested key type is incorrect: requested {requested} but key is {actual:?}")]
+    WrongRequestedKeyType {
+        /// Rust type requested by the caller
+        requested: &'static str,
+        /// Native type of the key reported by ecCodes
+        actual: NativeKeyType,
+    },
+
+    /// Returned when the key has a value that cannot be represented by requested type,
+    /// eg. when reading as `bool` a key with value other than `0` or `1`.
+    #[error("Requested key {0} has a value out of expected range")]
+    UnexpectedKeyValue(String),
+
+    /// Returned by [`CodesHandle::new_from_file_validated()`](crate::CodesHandle::new_from_file_validated)
+    /// when the file does not contain any message, eg. because it is not a GRIB file.
+    #[error("The file does not contain any messages")]
+    NoMessages,
+
+    /// Returned when the requested gridpoint index is not lower than the number of gridpoints in the message.
+    #[error("Gridpoint index {index} is out of range for a grid of {len} points")]
+    GridpointOutOfRange {
+        /// Requested index
+        index: usize,
+        /// Number of gridpoints in the message
+        len: usize,
+    },
+
+    /// Returned when the operation does not support the grid on which the message is defined.
+    #[error("The operation is not supported for messages on grid {0:?}")]
+    UnsupportedSourceGrid(GridType),
+
+    /// Returned when the requested BUFR subset is 0 or greater than the number of subsets in the message.
+    #[error("Subset {subset} is out of range for a message with {count} subsets")]
+    SubsetOutOfRange {
+        /// Requested subset
+        subset: usize,
+        /// Number of subsets in the message
+        count: usize,
+    },
+
+    /// Returned by [`KeyedMessage::set_values_clamped()`](crate::KeyedMessage::set_values_clamped)
+    /// when `min` is greater than `max` or any of them is NaN.
+    #[error("Invalid clamping range [{min}, {max}]")]
+    InvalidClampRange {
+        /// Requested lower bound
+        min: f64,
+        /// Requested upper bound
+        max: f64,
+    },
+
+    /// Returned by [`KeyedMessage::values_converted()`](crate::KeyedMessage::values_converted)
+    /// when conversion between given units is not supported.
+    #[error("Conversion of values from {from} to {to} is not supported")]
+    UnsupportedUnitConversion {
+        /// Units of the message
+        from: String,
+        /// Requested units
+        to: String,
+    },
+
+    /// Returned by [`CodesContext::check_definitions()`](crate::CodesContext::check_definitions)
+    /// when ecCodes cannot load its definitions or samples.
+    #[error("ecCodes definitions or samples cannot be loaded: {hint}")]
+    DefinitionsNotFound {
+        /// Suggestion how to fix the installation
+        hint: &'static str,
+    },
 
     /// Returned when [`eccodes_sys::codes_handle_clone`] returns null pointer
     /// indicating issues with cloning the message.
@@ -72,6 +178,41 @@ pub enum CodesError {
     #[error("Null pointer encountered where it should not be")]
     NullPtr,
 
+    /// Returned when the global mutex guarding `codes_index` functions cannot be acquired
+    /// within the timeout set with [`CodesIndex::with_lock_timeout()`](crate::CodesIndex::with_lock_timeout).
+    #[cfg(feature = "experimental_index")]
+    #[error("Cannot acquire the index mutex within {0:?}")]
+    MutexTimeout(std::time::Duration),
+
+    /// Returned by [`CodesIndex::select_checked()`](crate::CodesIndex::select_checked)
+    /// when no message in the index matches the selection.
+    #[cfg(feature = "experimental_index")]
+    #[error("No messages in the index match {key} = {value}")]
+    NoMatchingMessages {
+        /// Key used in the selection
+        key: String,
+        /// Selected value converted to string
+        value: String,
+    },
+
+    /// Returned by [`CodesIndex::add_grib_files()`](crate::CodesIndex::add_grib_files)
+    /// when one of files cannot be added to the index, with the path of that file.
+    #[cfg(feature = "experimental_index")]
+    #[error("Cannot add the file {} to the index: {source}", path.display())]
+    IndexAddFile {
+        /// Path of the file that failed to be added
+        path: PathBuf,
+        /// Underlying error
+        #[source]
+        source: Box<CodesError>,
+    },
+
+    /// Returned when the file passed to [`new_from_gzip_file()`](crate::CodesHandle::new_from_gzip_file)
+    /// does not start with gzip magic bytes.
+    #[cfg(feature = "flate2")]
+    #[error("The file is not gzip-compressed")]
+    NotGzipped,
+
     /// Returned when function in `message_ndarray` module cannot convert
     /// the message to ndarray. Check [`MessageNdarrayError`] for more details.
     #[cfg(feature = "message_ndarray")]
@@ -79,6 +220,42 @@ pub enum CodesError {
     NdarrayConvert(#[from] MessageNdarrayError),
 }
 
+impl From<CodesInternal> for CodesError {
+    fn from(error: CodesInternal) -> Self {
+        match error {
+            CodesInternal::CodesFunctionalityNotEnabled => CodesError::FunctionalityNotEnabled {
+                hint: FUNCTIONALITY_NOT_ENABLED_HINT,
+            },
+            error => CodesError::Internal(error),
+        }
+    }
+}
+
+impl CodesError {
+    /// Returns `true` when the error indicates corrupt input data rather than incorrect usage of the crate.
+    ///
+    /// Those are [`CodesInternal`] errors `CodesDecodingError`, `CodesMessageMalformed`,
+    /// `CodesCorruptedIndex` and `CodesWrongLength`. Errors wrapped in [`CodesError::IndexAddFile`]
+    /// are classified by their source.
+    #[must_use]
+    pub fn is_corrupt_data(&self) -> bool {
+        match self {
+            CodesError::Internal(
+                CodesInternal::CodesDecodingError
+                | CodesInternal::CodesMessageMalformed
+                | CodesInternal::CodesCorruptedIndex
+                | CodesInternal::CodesWrongLength,
+            ) => true,
+            #[cfg(feature = "experimental_index")]
+            CodesError::IndexAddFile { source, .. } => source.is_corrupt_data(),
+            _ => false,
+        }
+    }
+}
+
+const FUNCTIONALITY_NOT_ENABLED_HINT: &str = "ecCodes was probably built without support \
+for the packing type of this message (check ENABLE_JPG, ENABLE_PNG and ENABLE_AEC build options)";
+
 /// Errors returned by the `message_ndarray` module.
 #[cfg(feature = "message_ndarray")]
 #[cfg_attr(docsrs, doc(cfg(feature = "message_ndarray")))]
@@ -94,11 +271,21 @@ pub enum MessageNdarrayError {
     #[error("The length of the values array ({0}) is different than expected ({1})")]
     UnexpectedValuesLength(usize, usize),
 
+    /// Returned when the message contains a bitmap and length of values array
+    /// is equal neither to product of Ni and Nj keys nor to the number of gridpoints present in the bitmap.
+    #[error("The length of the values array ({0}) is different than the number of gridpoints present in the bitmap ({1})")]
+    UnexpectedBitmapValuesLength(usize, usize),
+
     /// Returned when functions converting to ndarray cannot correctly
     /// parse key necessary for the conversion.
     #[error("Requested key {0} has a value out of expected range")]
     UnexpectedKeyValue(String),
 
+    /// Returned when the layout of gridpoints cannot be represented as a regular 2D array,
+    /// eg. on staggered grids where points in alternate rows are offset.
+    #[error("The grid is not supported for ndarray conversion: {0}")]
+    UnsupportedGrid(&'static str),
+
     /// Returned when ndarray cannot create an array with the shape
     /// defined by Ni and Nj keys.
     #[error("Error occured while converting to ndarray: {0}")]
@@ -106,13 +293,18 @@ pub enum MessageNdarrayError {
 
     /// This error can occur when casting types of shape fails
     /// on 32-bit systems or for very large arrays.
-    #[error(transparent)]
+    #[error("Cannot cast the shape of the array: {0}")]
     IntCasting(#[from] std::num::TryFromIntError),
 }
 
 ///Errors returned by internal ecCodes library functions.
 ///Copied directly from the ecCodes API.
-#[derive(Copy, Eq, PartialEq, Clone, Ord, PartialOrd, Hash, Error, Debug, FromPrimitive)]
+///
+///Codes not known to this crate (eg. added in newer ecCodes versions)
+///are represented by [`CodesInternal::Unknown`], so more variants may be added in the future.
+#[derive(Copy, Eq, PartialEq, Clone, Ord, PartialOrd, Hash, Error, Debug)]
+#[repr(i32)]
+#[non_exhaustive]
 pub enum CodesInternal {
     ///No error
     #[error("No error")]
@@ -385,4 +577,153 @@ pub enum CodesInternal {
     ///Functionality not enabled
     #[error("Functionality not enabled")]
     CodesFunctionalityNotEnabled = -67,
+
+    ///Error code not known to this crate
+    #[error("Unknown error code {0}")]
+    // ecCodes error codes are never positive, so the discriminant cannot clash with them
+    Unknown(i32) = 1,
+}
+
+impl From<i32> for CodesInternal {
+    fn from(error_code: i32) -> Self {
+        match error_code {
+            0 => CodesInternal::CodesSuccess,
+            -1 => CodesInternal::CodesEndOfFile,
+            -2 => CodesInternal::CodesInternalError,
+            -3 => CodesInternal::CodesBufferTooSmall,
+            -4 => CodesInternal::CodesNotImplemented,
+            -5 => CodesInternal::Codes7777NotFound,
+            -6 => CodesInternal::CodesArrayTooSmall,
+            -7 => CodesInternal::CodesFileNotFound,
+            -8 => CodesInternal::CodesCodeNotFoundInTable,
+            -9 => CodesInternal::CodesWrongArraySize,
+            -10 => CodesInternal::CodesNotFound,
+            -11 => CodesInternal::CodesIoProblem,
+            -12 => CodesInternal::CodesInvalidMessage,
+            -13 => CodesInternal::CodesDecodingError,
+            -14 => CodesInternal::CodesEncodingError,
+            -15 => CodesInternal::CodesNoMoreInSet,
+            -16 => CodesInternal::CodesGeocalculusProblem,
+            -17 => CodesInternal::CodesOutOfMemory,
+            -18 => CodesInternal::CodesReadOnly,
+            -19 => CodesInternal::CodesInvalidArgument,
+            -20 => CodesInternal::CodesNullHandle,
+            -21 => CodesInternal::CodesInvalidSectionNumber,
+            -22 => CodesInternal::CodesValueCannotBeMissing,
+            -23 => CodesInternal::CodesWrongLength,
+            -24 => CodesInternal::CodesInvalidType,
+            -25 => CodesInternal::CodesWrongStep,
+            -26 => CodesInternal::CodesWrongStepUnit,
+            -27 => CodesInternal::CodesInvalidFile,
+            -28 => CodesInternal::CodesInvalidGrib,
+            -29 => CodesInternal::CodesInvalidIndex,
+            -30 => CodesInternal::CodesInvalidIterator,
+            -31 => CodesInternal::CodesInvalidKeysIterator,
+            -32 => CodesInternal::CodesInvalidNearest,
+            -33 => CodesInternal::CodesInvalidOrderby,
+            -34 => CodesInternal::CodesMissingKey,
+            -35 => CodesInternal::CodesOutOfArea,
+            -36 => CodesInternal::CodesConceptNoMatch,
+            -37 => CodesInternal::CodesHashArrayNoMatch,
+            -38 => CodesInternal::CodesNoDefinitions,
+            -39 => CodesInternal::CodesWrongType,
+            -40 => CodesInternal::CodesEnd,
+            -41 => CodesInternal::CodesNoValues,
+            -42 => CodesInternal::CodesWrongGrid,
+            -43 => CodesInternal::CodesEndOfIndex,
+            -44 => CodesInternal::CodesNullIndex,
+            -45 => CodesInternal::CodesPrematureEndOfFile,
+            -46 => CodesInternal::CodesInternalArrayTooSmall,
+            -47 => CodesInternal::CodesMessageTooLarge,
+            -48 => CodesInternal::CodesConstantField,
+            -49 => CodesInternal::CodesSwitchNoMatch,
+            -50 => CodesInternal::CodesUnderflow,
+            -51 => CodesInternal::CodesMessageMalformed,
+            -52 => CodesInternal::CodesCorruptedIndex,
+            -53 => CodesInternal::CodesInvalidBpv,
+            -54 => CodesInternal::CodesDifferentEdition,
+            -55 => CodesInternal::CodesValueDifferent,
+            -56 => CodesInternal::CodesInvalidKeyValue,
+            -57 => CodesInternal::CodesStringTooSmall,
+            -58 => CodesInternal::CodesWrongConversion,
+            -59 => CodesInternal::CodesMissingBufrEntry,
+            -60 => CodesInternal::CodesNullPointer,
+            -61 => CodesInternal::CodesAttributeClash,
+            -62 => CodesInternal::CodesTooManyAttributes,
+            -63 => CodesInternal::CodesAttributeNotFound,
+            -64 => CodesInternal::CodesUnsupportedEdition,
+            -65 => CodesInternal::CodesOutOfRange,
+            -66 => CodesInternal::CodesWrongBitmapSize,
+            -67 => CodesInternal::CodesFunctionalityNotEnabled,
+            code => CodesInternal::Unknown(code),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::{CodesError, CodesInternal};
+
+    #[test]
+    fn functionality_not_enabled_hint() {
+        let error = CodesError::from(CodesInternal::CodesFunctionalityNotEnabled);
+
+        assert!(matches!(error, CodesError::FunctionalityNotEnabled { .. }));
+        assert!(error.to_string().contains("built without support"));
+        assert!(error.to_string().contains("ENABLE_JPG"));
+
+        let error = CodesError::from(CodesInternal::CodesNotFound);
+        assert!(matches!(
+            error,
+            CodesError::Internal(CodesInternal::CodesNotFound)
+        ));
+    }
+
+    #[test]
+    fn corrupt_data_classification() {
+        for internal in [
+            CodesInternal::CodesDecodingError,
+            CodesInternal::CodesMessageMalformed,
+            CodesInternal::CodesCorruptedIndex,
+            CodesInternal::CodesWrongLength,
+        ] {
+            assert!(CodesError::from(internal).is_corrupt_data());
+        }
+
+        assert!(!CodesError::from(CodesInternal::CodesNotFound).is_corrupt_data());
+        assert!(!CodesError::UnexpectedKeyValue("Ni".to_owned()).is_corrupt_data());
+    }
+
+    #[test]
+    fn internal_from_code() {
+        assert_eq!(CodesInternal::from(0), CodesInternal::CodesSuccess);
+        assert_eq!(CodesInternal::from(-10), CodesInternal::CodesNotFound);
+        assert_eq!(
+            CodesInternal::from(-67),
+            CodesInternal::CodesFunctionalityNotEnabled
+        );
+        assert_eq!(CodesInternal::from(-999), CodesInternal::Unknown(-999));
+        assert_eq!(CodesInternal::from(1), CodesInternal::Unknown(1));
+    }
+
+    #[test]
+    #[cfg(feature = "message_ndarray")]
+    fn ndarray_error_source_chain() {
+        use crate::errors::MessageNdarrayError;
+        use ndarray::{Array2, ShapeError};
+        use std::error::Error;
+
+        let shape_error = Array2::from_shape_vec((2, 2), vec![0.0; 3]).unwrap_err();
+        let error = CodesError::from(MessageNdarrayError::from(shape_error));
+
+        let ndarray_error = error.source().unwrap();
+        assert!(ndarray_error.is::<MessageNdarrayError>());
+        assert!(ndarray_error.source().unwrap().is::<ShapeError>());
+
+        let cast_error = u8::try_from(256_i64).unwrap_err();
+        let error = CodesError::from(MessageNdarrayError::from(cast_error));
+
+        let inner = error.source().unwrap().source().unwrap();
+        assert!(inner.is::<std::num::TryFromIntError>());
+    }
 }
@@ -0,0 +1,22 @@
+#![allow(non_camel_case_types)]
+#![allow(clippy::module_name_repetitions)]
+
+use std::{ffi::CStr, ptr};
+
+use eccodes_sys::codes_context;
+
+use crate::errors::CodesError;
+
+pub unsafe fn codes_samples_path() -> Result<Option<String>, CodesError> {
+    let context: *mut codes_context = ptr::null_mut(); //default context
+
+    let path_pointer = eccodes_sys::codes_samples_path(context);
+
+    if path_pointer.is_null() {
+        return Ok(None);
+    }
+
+    let path = CStr::from_ptr(path_pointer).to_str()?.to_owned();
+
+    Ok(Some(path))
+}
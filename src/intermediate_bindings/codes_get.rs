@@ -1,7 +1,7 @@
 #![allow(non_camel_case_types)]
 #![allow(clippy::module_name_repetitions)]
 
-use std::ffi::{CStr, CString};
+use std::ffi::CString;
 
 use eccodes_sys::codes_handle;
 use libc::c_void;
@@ -9,11 +9,10 @@ use num_traits::FromPrimitive;
 
 use crate::{
     errors::{CodesError, CodesInternal},
+    keyed_message::NativeKeyType,
     pointer_guard,
 };
 
-use super::NativeKeyType;
-
 pub unsafe fn codes_get_native_type(
     handle: *const codes_handle,
     key: &str,
@@ -26,7 +25,7 @@ pub unsafe fn codes_get_native_type(
     let error_code = eccodes_sys::codes_get_native_type(handle, key.as_ptr(), &mut key_type);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -42,7 +41,7 @@ pub unsafe fn codes_get_size(handle: *const codes_handle, key: &str) -> Result<u
     let error_code = eccodes_sys::codes_get_size(handle, key.as_ptr(), &mut key_size);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -58,7 +57,7 @@ pub unsafe fn codes_get_long(handle: *const codes_handle, key: &str) -> Result<i
     let error_code = eccodes_sys::codes_get_long(handle, key.as_ptr(), &mut key_value);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -74,7 +73,7 @@ pub unsafe fn codes_get_double(handle: *const codes_handle, key: &str) -> Result
     let error_code = eccodes_sys::codes_get_double(handle, key.as_ptr(), &mut key_value);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -100,13 +99,38 @@ pub unsafe fn codes_get_double_array(
     );
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
     Ok(key_values)
 }
 
+pub unsafe fn codes_get_double_array_into(
+    handle: *const codes_handle,
+    key: &str,
+    key_values: &mut [f64],
+) -> Result<(), CodesError> {
+    pointer_guard::non_null!(handle);
+
+    let mut key_size = key_values.len();
+    let key = CString::new(key).unwrap();
+
+    let error_code = eccodes_sys::codes_get_double_array(
+        handle,
+        key.as_ptr(),
+        key_values.as_mut_ptr(),
+        &mut key_size,
+    );
+
+    if error_code != 0 {
+        let err = CodesInternal::from(error_code);
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
 pub unsafe fn codes_get_long_array(
     handle: *const codes_handle,
     key: &str,
@@ -126,7 +150,7 @@ pub unsafe fn codes_get_long_array(
     );
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -145,7 +169,7 @@ pub unsafe fn codes_get_length(
     let error_code = eccodes_sys::codes_get_length(handle, key.as_ptr(), &mut key_length);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -158,7 +182,9 @@ pub unsafe fn codes_get_string(
 ) -> Result<String, CodesError> {
     pointer_guard::non_null!(handle);
 
-    let mut key_length = codes_get_length(handle, key)?;
+    // one extra byte guarantees the nul termination,
+    // as some keys report their length without it
+    let mut key_length = codes_get_length(handle, key)? + 1;
     let key = CString::new(key).unwrap();
 
     let mut key_message: Vec<u8> = vec![0; key_length];
@@ -171,21 +197,15 @@ pub unsafe fn codes_get_string(
     );
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
-    key_message.truncate(key_length);
-    let key_message_result = CStr::from_bytes_with_nul(key_message.as_ref());
-
-    let key_message_cstr = if let Ok(msg) = key_message_result {
-        msg
-    } else {
-        key_message.push(0);
-        CStr::from_bytes_with_nul(key_message.as_ref())?
-    };
-
-    let key_message_string = key_message_cstr.to_str()?.to_string();
+    let end = key_message
+        .iter()
+        .position(|&byte| byte == 0)
+        .unwrap_or(key_message.len());
+    let key_message_string = std::str::from_utf8(&key_message[..end])?.to_string();
 
     Ok(key_message_string)
 }
@@ -209,7 +229,7 @@ pub unsafe fn codes_get_bytes(
     );
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -224,7 +244,7 @@ pub unsafe fn codes_get_message_size(handle: *const codes_handle) -> Result<usiz
     let error_code = eccodes_sys::codes_get_message_size(handle, &mut size);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -246,7 +266,7 @@ pub unsafe fn codes_get_message(
     let error_code = eccodes_sys::codes_get_message(handle, &mut buffer_ptr, &mut message_size);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
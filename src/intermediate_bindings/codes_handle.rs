@@ -1,13 +1,15 @@
 #![allow(non_camel_case_types)]
 #![allow(clippy::module_name_repetitions)]
 
-use std::ptr::{self};
+use std::{
+    ffi::CString,
+    ptr::{self},
+};
 
 use eccodes_sys::{codes_context, codes_handle};
 #[cfg(feature = "experimental_index")]
 use eccodes_sys::{codes_index, CODES_LOCK};
 use libc::FILE;
-use num_traits::FromPrimitive;
 
 use crate::{
     codes_handle::ProductKind,
@@ -39,13 +41,33 @@ pub unsafe fn codes_handle_new_from_file(
     );
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
     Ok(file_handle)
 }
 
+/// Counts messages from the current position to the end of the stream.
+/// The stream is left at its end.
+pub unsafe fn codes_count_in_file(file_pointer: *mut FILE) -> Result<usize, CodesError> {
+    pointer_guard::non_null!(file_pointer);
+
+    let context: *mut codes_context = ptr::null_mut(); //default context
+
+    let mut count: i32 = 0;
+
+    let error_code =
+        eccodes_sys::codes_count_in_file(context, file_pointer.cast::<_SYS_IO_FILE>(), &mut count);
+
+    if error_code != 0 {
+        let err = CodesInternal::from(error_code);
+        return Err(err.into());
+    }
+
+    Ok(usize::try_from(count).unwrap_or_default())
+}
+
 pub unsafe fn codes_handle_delete(handle: *mut codes_handle) -> Result<(), CodesError> {
     #[cfg(test)]
     log::trace!("codes_handle_delete");
@@ -57,7 +79,7 @@ pub unsafe fn codes_handle_delete(handle: *mut codes_handle) -> Result<(), Codes
     let error_code = eccodes_sys::codes_handle_delete(handle);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -67,12 +89,42 @@ pub unsafe fn codes_handle_delete(handle: *mut codes_handle) -> Result<(), Codes
 #[cfg(feature = "experimental_index")]
 pub unsafe fn codes_handle_new_from_index(
     index: *mut codes_index,
+) -> Result<*mut codes_handle, CodesError> {
+    let _g = CODES_LOCK.lock().unwrap();
+    codes_handle_new_from_index_unlocked(index)
+}
+
+/// Creates handles for all messages left in the index, holding `CODES_LOCK` for the whole loop.
+/// Already created handles are deleted if any of the subsequent calls fails.
+#[cfg(feature = "experimental_index")]
+pub unsafe fn codes_handle_new_all_from_index(
+    index: *mut codes_index,
+) -> Result<Vec<*mut codes_handle>, CodesError> {
+    let _g = CODES_LOCK.lock().unwrap();
+    let mut handles = vec![];
+
+    loop {
+        match codes_handle_new_from_index_unlocked(index) {
+            Ok(handle) if handle.is_null() => return Ok(handles),
+            Ok(handle) => handles.push(handle),
+            Err(err) => {
+                for handle in handles {
+                    let _ = codes_handle_delete(handle);
+                }
+                return Err(err);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "experimental_index")]
+unsafe fn codes_handle_new_from_index_unlocked(
+    index: *mut codes_index,
 ) -> Result<*mut codes_handle, CodesError> {
     pointer_guard::non_null!(index);
 
     let mut error_code: i32 = 0;
 
-    let _g = CODES_LOCK.lock().unwrap();
     let codes_handle = eccodes_sys::codes_handle_new_from_index(index, &mut error_code);
 
     // special case! codes_handle_new_from_index returns -43 when there are no messages left in the index
@@ -82,7 +134,7 @@ pub unsafe fn codes_handle_new_from_index(
     }
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
     Ok(codes_handle)
@@ -101,3 +153,12 @@ pub unsafe fn codes_handle_clone(
 
     Ok(clone_handle)
 }
+
+pub unsafe fn codes_handle_new_from_samples(sample_name: &str) -> *mut codes_handle {
+    let context: *mut codes_context = ptr::null_mut(); //default context
+
+    let sample_name = CString::new(sample_name).unwrap();
+
+    // returns null pointer when sample cannot be loaded, which is handled upstream
+    eccodes_sys::codes_handle_new_from_samples(context, sample_name.as_ptr())
+}
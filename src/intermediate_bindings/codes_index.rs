@@ -2,8 +2,13 @@
 #![allow(clippy::module_name_repetitions)]
 
 use eccodes_sys::{codes_context, codes_index, CODES_LOCK};
-use num_traits::FromPrimitive;
-use std::{ffi::CString, ptr};
+use std::{
+    ffi::CString,
+    ptr,
+    sync::{MutexGuard, TryLockError},
+    thread,
+    time::{Duration, Instant},
+};
 
 use crate::{
     errors::{CodesError, CodesInternal},
@@ -13,6 +18,28 @@ use crate::{
 // all index functions are safeguarded by a lock
 // because there are random errors appearing when using the index functions concurrently
 
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Acquires `CODES_LOCK`, waiting at most `timeout` if it is provided.
+/// `std::sync::Mutex` has no `try_lock_for`, so the lock is polled until the deadline.
+fn lock_with_timeout(timeout: Option<Duration>) -> Result<MutexGuard<'static, ()>, CodesError> {
+    let Some(timeout) = timeout else {
+        return Ok(CODES_LOCK.lock().unwrap());
+    };
+
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        match CODES_LOCK.try_lock() {
+            Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                thread::sleep(LOCK_POLL_INTERVAL);
+            }
+            Err(TryLockError::WouldBlock) => return Err(CodesError::MutexTimeout(timeout)),
+            result => return Ok(result.unwrap()),
+        }
+    }
+}
+
 pub unsafe fn codes_index_new(keys: &str) -> Result<*mut codes_index, CodesError> {
     let context: *mut codes_context = ptr::null_mut(); //default context
     let mut error_code: i32 = 0;
@@ -22,7 +49,31 @@ pub unsafe fn codes_index_new(keys: &str) -> Result<*mut codes_index, CodesError
     let codes_index = eccodes_sys::codes_index_new(context, keys.as_ptr(), &mut error_code);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
+        return Err(err.into());
+    }
+    Ok(codes_index)
+}
+
+pub unsafe fn codes_index_new_from_file(
+    filename: &str,
+    keys: &str,
+) -> Result<*mut codes_index, CodesError> {
+    let context: *mut codes_context = ptr::null_mut(); //default context
+    let mut error_code: i32 = 0;
+    let filename = CString::new(filename).unwrap();
+    let keys = CString::new(keys).unwrap();
+
+    let _g = CODES_LOCK.lock().unwrap();
+    let codes_index = eccodes_sys::codes_index_new_from_file(
+        context,
+        filename.as_ptr(),
+        keys.as_ptr(),
+        &mut error_code,
+    );
+
+    if error_code != 0 {
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
     Ok(codes_index)
@@ -37,12 +88,27 @@ pub unsafe fn codes_index_read(filename: &str) -> Result<*mut codes_index, Codes
     let codes_index = eccodes_sys::codes_index_read(context, filename.as_ptr(), &mut error_code);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
     Ok(codes_index)
 }
 
+pub unsafe fn codes_index_write(index: *mut codes_index, filename: &str) -> Result<(), CodesError> {
+    pointer_guard::non_null!(index);
+
+    let filename = CString::new(filename).unwrap();
+
+    let _g = CODES_LOCK.lock().unwrap();
+    let error_code = eccodes_sys::codes_index_write(index, filename.as_ptr());
+
+    if error_code != 0 {
+        let err = CodesInternal::from(error_code);
+        return Err(err.into());
+    }
+    Ok(())
+}
+
 pub unsafe fn codes_index_delete(index: *mut codes_index) {
     #[cfg(test)]
     log::trace!("codes_index_delete");
@@ -58,16 +124,17 @@ pub unsafe fn codes_index_delete(index: *mut codes_index) {
 pub unsafe fn codes_index_add_file(
     index: *mut codes_index,
     filename: &str,
+    lock_timeout: Option<Duration>,
 ) -> Result<(), CodesError> {
     pointer_guard::non_null!(index);
 
     let filename = CString::new(filename).unwrap();
 
-    let _g = CODES_LOCK.lock().unwrap();
+    let _g = lock_with_timeout(lock_timeout)?;
     let error_code = eccodes_sys::codes_index_add_file(index, filename.as_ptr());
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
     Ok(())
@@ -77,16 +144,17 @@ pub unsafe fn codes_index_select_long(
     index: *mut codes_index,
     key: &str,
     value: i64,
+    lock_timeout: Option<Duration>,
 ) -> Result<(), CodesError> {
     pointer_guard::non_null!(index);
 
     let key = CString::new(key).unwrap();
 
-    let _g = CODES_LOCK.lock().unwrap();
+    let _g = lock_with_timeout(lock_timeout)?;
     let error_code = eccodes_sys::codes_index_select_long(index, key.as_ptr(), value);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
     Ok(())
@@ -96,16 +164,17 @@ pub unsafe fn codes_index_select_double(
     index: *mut codes_index,
     key: &str,
     value: f64,
+    lock_timeout: Option<Duration>,
 ) -> Result<(), CodesError> {
     pointer_guard::non_null!(index);
 
     let key = CString::new(key).unwrap();
 
-    let _g = CODES_LOCK.lock().unwrap();
+    let _g = lock_with_timeout(lock_timeout)?;
     let error_code = eccodes_sys::codes_index_select_double(index, key.as_ptr(), value);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
     Ok(())
@@ -115,17 +184,18 @@ pub unsafe fn codes_index_select_string(
     index: *mut codes_index,
     key: &str,
     value: &str,
+    lock_timeout: Option<Duration>,
 ) -> Result<(), CodesError> {
     pointer_guard::non_null!(index);
 
     let key = CString::new(key).unwrap();
     let value = CString::new(value).unwrap();
 
-    let _g = CODES_LOCK.lock().unwrap();
+    let _g = lock_with_timeout(lock_timeout)?;
     let error_code = eccodes_sys::codes_index_select_string(index, key.as_ptr(), value.as_ptr());
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
     Ok(())
@@ -5,8 +5,6 @@ use std::ffi::{CStr, CString};
 
 use eccodes_sys::{codes_handle, codes_keys_iterator};
 
-use num_traits::FromPrimitive;
-
 use crate::{
     errors::{CodesError, CodesInternal},
     pointer_guard,
@@ -43,7 +41,7 @@ pub unsafe fn codes_keys_iterator_delete(
     let error_code = eccodes_sys::codes_keys_iterator_delete(keys_iterator);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
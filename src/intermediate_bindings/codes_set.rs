@@ -5,10 +5,9 @@ use std::ffi::CString;
 
 use eccodes_sys::codes_handle;
 
-use num_traits::FromPrimitive;
-
 use crate::{
     errors::{CodesError, CodesInternal},
+    keyed_message::NativeKeyType,
     pointer_guard,
 };
 
@@ -24,7 +23,7 @@ pub unsafe fn codes_set_long(
     let error_code = eccodes_sys::codes_set_long(handle, key.as_ptr(), value);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -43,7 +42,7 @@ pub unsafe fn codes_set_double(
     let error_code = eccodes_sys::codes_set_double(handle, key.as_ptr(), value);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -69,7 +68,7 @@ pub unsafe fn codes_set_long_array(
     );
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -95,7 +94,7 @@ pub unsafe fn codes_set_double_array(
     );
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -117,7 +116,7 @@ pub unsafe fn codes_set_string(
         eccodes_sys::codes_set_string(handle, key.as_ptr(), value.as_ptr(), &mut length);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -143,7 +142,29 @@ pub unsafe fn codes_set_bytes(
     );
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
+        return Err(err.into());
+    }
+
+    Ok(())
+}
+
+pub unsafe fn codes_copy_key(
+    source: *mut codes_handle,
+    destination: *mut codes_handle,
+    key: &str,
+    key_type: NativeKeyType,
+) -> Result<(), CodesError> {
+    pointer_guard::non_null!(source);
+    pointer_guard::non_null!(destination);
+
+    let key = CString::new(key).unwrap();
+
+    let error_code =
+        eccodes_sys::codes_copy_key(source, destination, key.as_ptr(), key_type as i32);
+
+    if error_code != 0 {
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
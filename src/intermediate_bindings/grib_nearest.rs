@@ -3,9 +3,7 @@
 
 use std::ptr::addr_of_mut;
 
-use eccodes_sys::{codes_handle, codes_nearest, CODES_NEAREST_SAME_DATA, CODES_NEAREST_SAME_GRID};
-
-use num_traits::FromPrimitive;
+use eccodes_sys::{codes_handle, codes_nearest};
 
 use crate::{
     errors::{CodesError, CodesInternal},
@@ -22,7 +20,7 @@ pub unsafe fn codes_grib_nearest_new(
     let nearest = eccodes_sys::codes_grib_nearest_new(handle, &mut error_code);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -40,7 +38,7 @@ pub unsafe fn codes_grib_nearest_delete(nearest: *mut codes_nearest) -> Result<(
     let error_code = eccodes_sys::codes_grib_nearest_delete(nearest);
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -52,14 +50,11 @@ pub unsafe fn codes_grib_nearest_find(
     nearest: *mut codes_nearest,
     lat: f64,
     lon: f64,
+    flags: u32,
 ) -> Result<[NearestGridpoint; 4], CodesError> {
     pointer_guard::non_null!(handle);
     pointer_guard::non_null!(nearest);
 
-    // such flags are set because find nearest for given nearest is always
-    // called on the same grib message
-    let flags = CODES_NEAREST_SAME_GRID + CODES_NEAREST_SAME_DATA;
-
     let mut output_lats = [0_f64; 4];
     let mut output_lons = [0_f64; 4];
     let mut output_values = [0_f64; 4];
@@ -83,7 +78,7 @@ pub unsafe fn codes_grib_nearest_find(
     );
 
     if error_code != 0 {
-        let err: CodesInternal = FromPrimitive::from_i32(error_code).unwrap();
+        let err = CodesInternal::from(error_code);
         return Err(err.into());
     }
 
@@ -0,0 +1,121 @@
+use crate::{errors::CodesError, KeyRead, KeyedMessage};
+
+impl KeyedMessage {
+    /// Returns the number of subsets in a BUFR message (`numberOfSubsets` key).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when the key cannot be read, eg. when the message is not BUFR.
+    pub fn subset_count(&self) -> Result<usize, CodesError> {
+        let count: i64 = self.read_key("numberOfSubsets")?;
+
+        usize::try_from(count)
+            .map_err(|_| CodesError::UnexpectedKeyValue("numberOfSubsets".to_owned()))
+    }
+
+    /// Reads an element key of given subset of a BUFR message, using ecCodes `#n#name` syntax.
+    /// Subsets are numbered from 1, as in ecCodes.
+    ///
+    /// Data section of BUFR message must be unpacked (by setting `unpack` key to 1)
+    /// before element keys can be read.
+    ///
+    /// Note that ecCodes interprets `n` as the rank of the occurrence of the key in the message.
+    /// It equals the subset number only when the element occurs once in each subset,
+    /// which is the case for most messages with uncompressed data.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    ///  use eccodes::{KeyedMessage, KeyWrite};
+    ///  # fn read_temperatures(mut message: KeyedMessage) -> anyhow::Result<()> {
+    ///  message.write_key("unpack", 1)?;
+    ///
+    ///  for subset in 1..=message.subset_count()? {
+    ///      let temperature: f64 = message.read_subset_key(subset, "airTemperature")?;
+    ///      println!("subset {subset}: {temperature} K");
+    ///  }
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::SubsetOutOfRange`] when `subset` is 0 or greater than the number of subsets.
+    ///
+    /// Returns [`CodesError`] when the key cannot be read as requested type.
+    pub fn read_subset_key<T>(&self, subset: usize, name: &str) -> Result<T, CodesError>
+    where
+        Self: KeyRead<T>,
+    {
+        let count = self.subset_count()?;
+
+        if subset == 0 || subset > count {
+            return Err(CodesError::SubsetOutOfRange { subset, count });
+        }
+
+        self.read_key(&format!("#{subset}#{name}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::intermediate_bindings::codes_handle_new_from_samples;
+    use crate::{CodesError, KeyWrite, KeyedMessage};
+
+    #[test]
+    fn bufr_sample_subsets() -> Result<()> {
+        let mut msg = KeyedMessage {
+            message_handle: unsafe { codes_handle_new_from_samples("BUFR4") },
+            keys_namespace: None,
+        };
+        assert!(!msg.message_handle.is_null());
+
+        msg.write_key("unpack", 1)?;
+        let count = msg.subset_count()?;
+        assert!(count >= 1);
+
+        let result: Result<i64, CodesError> = msg.read_subset_key(count + 1, "year");
+        assert!(matches!(
+            result,
+            Err(CodesError::SubsetOutOfRange { subset, .. }) if subset == count + 1
+        ));
+
+        let result: Result<i64, CodesError> = msg.read_subset_key(0, "year");
+        assert!(matches!(result, Err(CodesError::SubsetOutOfRange { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bufr_decode_subset_element() -> Result<()> {
+        let mut msg = KeyedMessage {
+            message_handle: unsafe { codes_handle_new_from_samples("BUFR4") },
+            keys_namespace: None,
+        };
+        assert!(!msg.message_handle.is_null());
+
+        // encode two uncompressed subsets with air temperature (descriptor 012101)
+        msg.write_key("numberOfSubsets", 2)?;
+        msg.write_key("compressedData", 0)?;
+        msg.write_key("unexpandedDescriptors", &[12101_i64][..])?;
+        msg.write_key("#1#airTemperature", 290.15)?;
+        msg.write_key("#2#airTemperature", 271.65)?;
+        msg.write_key("pack", 1)?;
+
+        // the clone is created from the encoded bytes, so values are decoded again
+        let mut decoded = msg.try_clone()?;
+        decoded.write_key("unpack", 1)?;
+
+        assert_eq!(decoded.subset_count()?, 2);
+
+        let first: f64 = decoded.read_subset_key(1, "airTemperature")?;
+        let second: f64 = decoded.read_subset_key(2, "airTemperature")?;
+
+        assert!((first - 290.15).abs() < 1e-6);
+        assert!((second - 271.65).abs() < 1e-6);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,522 @@
+use super::regrid::grid_coordinate;
+use crate::{
+    errors::{CodesError, CodesInternal},
+    DynamicKeyType, KeyRead, KeyWrite, KeyedMessage,
+};
+
+/// Keys that together describe the geometry of the grid
+const GRID_KEYS: [&str; 12] = [
+    "gridType",
+    "Ni",
+    "Nj",
+    "latitudeOfFirstGridPointInDegrees",
+    "longitudeOfFirstGridPointInDegrees",
+    "latitudeOfLastGridPointInDegrees",
+    "longitudeOfLastGridPointInDegrees",
+    "iDirectionIncrementInDegrees",
+    "jDirectionIncrementInDegrees",
+    "iScansNegatively",
+    "jScansPositively",
+    "jPointsAreConsecutive",
+];
+
+/// Type of the grid on which the message is defined, as indicated by the `gridType` key.
+///
+/// Values not listed here are returned as [`GridType::Other`] with the raw key value.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum GridType {
+    /// Regular latitude-longitude grid (`regular_ll`)
+    RegularLatLon,
+    /// Reduced latitude-longitude grid (`reduced_ll`)
+    ReducedLatLon,
+    /// Rotated latitude-longitude grid (`rotated_ll`)
+    RotatedLatLon,
+    /// Regular Gaussian grid (`regular_gg`)
+    RegularGaussian,
+    /// Reduced Gaussian grid (`reduced_gg`)
+    ReducedGaussian,
+    /// Rotated Gaussian grid (`rotated_gg`)
+    RotatedGaussian,
+    /// Lambert conformal grid (`lambert`)
+    LambertConformal,
+    /// Polar stereographic grid (`polar_stereographic`)
+    PolarStereographic,
+    /// Mercator grid (`mercator`)
+    Mercator,
+    /// Spherical harmonics (`sh`)
+    SphericalHarmonics,
+    /// Any other grid type, with the value of `gridType` key
+    Other(String),
+}
+
+/// Geometry of a regular grid, as returned by [`KeyedMessage::grid_definition()`]
+/// and applied with [`KeyedMessage::set_grid_definition()`].
+///
+/// Coordinates and increments are in degrees.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GridDefinition {
+    /// Number of points along a parallel (`Ni`)
+    pub ni: i64,
+    /// Number of points along a meridian (`Nj`)
+    pub nj: i64,
+    /// Latitude of the first gridpoint
+    pub first_latitude: f64,
+    /// Longitude of the first gridpoint
+    pub first_longitude: f64,
+    /// Latitude of the last gridpoint
+    pub last_latitude: f64,
+    /// Longitude of the last gridpoint
+    pub last_longitude: f64,
+    /// Increment between gridpoints along a parallel
+    pub i_increment: f64,
+    /// Increment between gridpoints along a meridian
+    pub j_increment: f64,
+    /// Whether points along a parallel are scanned from east to west (`iScansNegatively`)
+    pub i_scans_negatively: bool,
+    /// Whether points along a meridian are scanned from south to north (`jScansPositively`)
+    pub j_scans_positively: bool,
+    /// Whether consecutive points are along a meridian (`jPointsAreConsecutive`)
+    pub j_points_are_consecutive: bool,
+}
+
+impl From<String> for GridType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "regular_ll" => GridType::RegularLatLon,
+            "reduced_ll" => GridType::ReducedLatLon,
+            "rotated_ll" => GridType::RotatedLatLon,
+            "regular_gg" => GridType::RegularGaussian,
+            "reduced_gg" => GridType::ReducedGaussian,
+            "rotated_gg" => GridType::RotatedGaussian,
+            "lambert" => GridType::LambertConformal,
+            "polar_stereographic" => GridType::PolarStereographic,
+            "mercator" => GridType::Mercator,
+            "sh" => GridType::SphericalHarmonics,
+            _ => GridType::Other(value),
+        }
+    }
+}
+
+impl KeyedMessage {
+    /// Reads the `gridType` key and maps it to [`GridType`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle, GridType};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  assert_eq!(message.grid_type()?, GridType::RegularLatLon);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when the `gridType` key cannot be read as a string.
+    pub fn grid_type(&self) -> Result<GridType, CodesError> {
+        let grid_type: String = self.read_key("gridType")?;
+
+        Ok(GridType::from(grid_type))
+    }
+
+    /// Reads a geographic key in degrees, given its base name (eg. `latitudeOfFirstGridPoint`).
+    ///
+    /// First tries to read `{base}InDegrees` key. If it is not present, reads the `{base}` key,
+    /// stored as integer, and divides it by the value of `angleSubdivisions` key
+    /// (`1e6` for GRIB2 and `1e3` for GRIB1), or by `1e6` when `angleSubdivisions` is not present.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let first_lat = message.read_degrees("latitudeOfFirstGridPoint")?;
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when neither of the keys can be read.
+    #[allow(clippy::cast_precision_loss)] // angles are far below f64 mantissa range
+    pub fn read_degrees(&self, base: &str) -> Result<f64, CodesError> {
+        match self.read_key(&format!("{base}InDegrees")) {
+            Err(CodesError::Internal(CodesInternal::CodesNotFound) | CodesError::MissingKey) => {}
+            result => return result,
+        }
+
+        let value: i64 = self.read_key(base)?;
+        let subdivisions = match self.read_key_dynamic_if_present("angleSubdivisions")? {
+            Some(DynamicKeyType::Int(subdivisions)) if subdivisions > 0 => subdivisions,
+            _ => 1_000_000,
+        };
+
+        Ok(value as f64 / subdivisions as f64)
+    }
+
+    /// Reads the geometry of a regular grid into [`GridDefinition`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let grid = message.grid_definition()?;
+    ///  assert_eq!((grid.ni, grid.nj), (49, 17));
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when any of the grid keys is not present in the message
+    /// or cannot be read, eg. for irregular grids without increments.
+    pub fn grid_definition(&self) -> Result<GridDefinition, CodesError> {
+        Ok(GridDefinition {
+            ni: self.read_key("Ni")?,
+            nj: self.read_key("Nj")?,
+            first_latitude: self.read_key("latitudeOfFirstGridPointInDegrees")?,
+            first_longitude: self.read_key("longitudeOfFirstGridPointInDegrees")?,
+            last_latitude: self.read_key("latitudeOfLastGridPointInDegrees")?,
+            last_longitude: self.read_key("longitudeOfLastGridPointInDegrees")?,
+            i_increment: self.read_key("iDirectionIncrementInDegrees")?,
+            j_increment: self.read_key("jDirectionIncrementInDegrees")?,
+            i_scans_negatively: self.read_key("iScansNegatively")?,
+            j_scans_positively: self.read_key("jScansPositively")?,
+            j_points_are_consecutive: self.read_key("jPointsAreConsecutive")?,
+        })
+    }
+
+    /// Applies the [`GridDefinition`] to the message, eg. to retarget a sample message onto a desired grid.
+    ///
+    /// Keys are written in the order respecting their dependencies in ecCodes: scanning flags first,
+    /// then number of points, coordinates of first and last gridpoints and finally increments.
+    ///
+    /// Data values are not modified, so the `values` of correct length must be written afterwards
+    /// when the number of points changes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  let mut grid = message.grid_definition()?;
+    ///  grid.first_latitude -= 1.0;
+    ///  grid.last_latitude -= 1.0;
+    ///
+    ///  message.set_grid_definition(&grid)?;
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`]
+    /// when any of the grid keys cannot be written.
+    pub fn set_grid_definition(&mut self, grid: &GridDefinition) -> Result<(), CodesError> {
+        self.write_key("iScansNegatively", i64::from(grid.i_scans_negatively))?
+            .write_key("jScansPositively", i64::from(grid.j_scans_positively))?
+            .write_key(
+                "jPointsAreConsecutive",
+                i64::from(grid.j_points_are_consecutive),
+            )?
+            .write_key("Ni", grid.ni)?
+            .write_key("Nj", grid.nj)?
+            .write_key("latitudeOfFirstGridPointInDegrees", grid.first_latitude)?
+            .write_key("longitudeOfFirstGridPointInDegrees", grid.first_longitude)?
+            .write_key("latitudeOfLastGridPointInDegrees", grid.last_latitude)?
+            .write_key("longitudeOfLastGridPointInDegrees", grid.last_longitude)?
+            .write_key("iDirectionIncrementInDegrees", grid.i_increment)?
+            .write_key("jDirectionIncrementInDegrees", grid.j_increment)?;
+
+        Ok(())
+    }
+
+    /// Checks whether both messages are defined on the same grid.
+    ///
+    /// Compares the grid type, number of points along both axes, coordinates of first and last gridpoints,
+    /// increments and scanning flags. Keys that are absent in both messages (eg. increments
+    /// in irregular grids) are treated as equal, keys present in only one of messages are treated as different.
+    ///
+    /// This is useful to check before combining data values of two messages,
+    /// as arrays on misaligned grids can be combined without any error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland-levels.grib"), ProductKind::GRIB)?;
+    ///  let first = handle.next()?.context("no message")?.try_clone()?;
+    ///  let second = handle.next()?.context("no message")?;
+    ///
+    ///  assert!(first.same_grid_as(second)?);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when any of grid keys exists in the message but cannot be read.
+    pub fn same_grid_as(&self, other: &KeyedMessage) -> Result<bool, CodesError> {
+        for key in GRID_KEYS {
+            if self.read_key_dynamic_if_present(key)? != other.read_key_dynamic_if_present(key)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Returns values of keys describing the grid, which are equal for messages on the same grid.
+    pub(crate) fn grid_signature(&self) -> Result<Vec<Option<DynamicKeyType>>, CodesError> {
+        GRID_KEYS
+            .iter()
+            .map(|key| self.read_key_dynamic_if_present(key))
+            .collect()
+    }
+
+    /// Returns `(latitude, longitude)` in degrees of the gridpoint at given index in `values`,
+    /// eg. the `index` of [`NearestGridpoint`](crate::codes_nearest::NearestGridpoint).
+    ///
+    /// For regular latitude-longitude grids the coordinate is computed from [`grid_definition()`](KeyedMessage::grid_definition),
+    /// so no coordinate arrays are decoded. For other grids the coordinate is taken
+    /// from `latitudes` and `longitudes` keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let (lat, lon) = message.coordinate_at(0)?;
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::GridpointOutOfRange`] when `index` is not lower than the number of gridpoints.
+    ///
+    /// Returns [`CodesError`] when grid keys or coordinate arrays cannot be read.
+    pub fn coordinate_at(&self, index: usize) -> Result<(f64, f64), CodesError> {
+        if self.grid_type()? != GridType::RegularLatLon {
+            let latitudes: Vec<f64> = self.read_key("latitudes")?;
+            let longitudes: Vec<f64> = self.read_key("longitudes")?;
+
+            return match (latitudes.get(index), longitudes.get(index)) {
+                (Some(lat), Some(lon)) => Ok((*lat, *lon)),
+                _ => Err(CodesError::GridpointOutOfRange {
+                    index,
+                    len: latitudes.len(),
+                }),
+            };
+        }
+
+        let grid = self.grid_definition()?;
+        let ni = usize::try_from(grid.ni)
+            .map_err(|_| CodesError::UnexpectedKeyValue("Ni".to_owned()))?;
+        let nj = usize::try_from(grid.nj)
+            .map_err(|_| CodesError::UnexpectedKeyValue("Nj".to_owned()))?;
+
+        if index >= ni * nj {
+            return Err(CodesError::GridpointOutOfRange {
+                index,
+                len: ni * nj,
+            });
+        }
+
+        let (i, j) = if grid.j_points_are_consecutive {
+            (index / nj, index % nj)
+        } else {
+            (index % ni, index / ni)
+        };
+
+        Ok(grid_coordinate(&grid, i, j))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{Context, Result};
+
+    use crate::codes_handle::{CodesHandle, ProductKind};
+    use crate::intermediate_bindings::codes_handle_new_from_samples;
+    use crate::{
+        CodesError, FallibleStreamingIterator, GridDefinition, GridType, KeyRead, KeyedMessage,
+    };
+    use std::path::Path;
+
+    #[test]
+    fn same_grid_same_file() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+
+        let first = handle.next()?.context("Message not some")?.try_clone()?;
+        let second = handle.next()?.context("Message not some")?;
+
+        assert!(first.same_grid_as(second)?);
+        assert!(second.same_grid_as(&first)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn same_grid_different_files() -> Result<()> {
+        let mut handle =
+            CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+        let iceland = handle.next()?.context("Message not some")?.try_clone()?;
+
+        let mut handle =
+            CodesHandle::new_from_file(Path::new("./data/gfs.grib"), ProductKind::GRIB)?;
+        let gfs = handle.next()?.context("Message not some")?;
+
+        assert!(!iceland.same_grid_as(gfs)?);
+        assert!(!gfs.same_grid_as(&iceland)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn grid_type() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+
+        let msg = handle.next()?.context("Message not some")?;
+
+        assert_eq!(msg.grid_type()?, GridType::RegularLatLon);
+
+        Ok(())
+    }
+
+    #[test]
+    fn grid_type_from_string() {
+        assert_eq!(
+            GridType::from("reduced_gg".to_string()),
+            GridType::ReducedGaussian
+        );
+        assert_eq!(
+            GridType::from("unstructured_grid".to_string()),
+            GridType::Other("unstructured_grid".to_string())
+        );
+    }
+
+    #[test]
+    fn read_degrees() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+
+        let msg = handle.next()?.context("Message not some")?;
+
+        let expected: f64 = msg.read_key("latitudeOfFirstGridPointInDegrees")?;
+        let degrees = msg.read_degrees("latitudeOfFirstGridPoint")?;
+        assert!((degrees - expected).abs() < 1e-6);
+
+        let expected: f64 = msg.read_key("longitudeOfLastGridPointInDegrees")?;
+        let degrees = msg.read_degrees("longitudeOfLastGridPoint")?;
+        assert!((degrees - expected).abs() < 1e-6);
+
+        // `La1` is an integer alias without `La1InDegrees` counterpart,
+        // so it is scaled by `angleSubdivisions`
+        assert!(msg.read_key_dynamic_if_present("La1InDegrees")?.is_none());
+        let expected: f64 = msg.read_key("latitudeOfFirstGridPointInDegrees")?;
+        let degrees = msg.read_degrees("La1")?;
+        assert!((degrees - expected).abs() < 1e-6);
+
+        assert!(msg.read_degrees("doesNotExist").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn set_grid_definition_on_sample() -> Result<()> {
+        let mut msg = KeyedMessage {
+            message_handle: unsafe { codes_handle_new_from_samples("GRIB2") },
+            keys_namespace: None,
+        };
+        assert!(!msg.message_handle.is_null());
+
+        let grid = GridDefinition {
+            ni: 49,
+            nj: 17,
+            first_latitude: 67.0,
+            first_longitude: 335.0,
+            last_latitude: 63.0,
+            last_longitude: 347.0,
+            i_increment: 0.25,
+            j_increment: 0.25,
+            i_scans_negatively: false,
+            j_scans_positively: false,
+            j_points_are_consecutive: false,
+        };
+
+        msg.set_grid_definition(&grid)?;
+
+        assert_eq!(msg.grid_definition()?, grid);
+
+        Ok(())
+    }
+
+    #[test]
+    fn coordinate_at_nearest_index() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let msg = handle.next()?.context("Message not some")?;
+
+        let nearest = msg.codes_nearest()?.find_nearest(64.13, -21.89)?;
+        let point = nearest
+            .iter()
+            .find(|point| point.index == 551)
+            .context("Gridpoint 551 not found")?;
+
+        let (lat, lon) = msg.coordinate_at(551)?;
+
+        assert!((lat - point.lat).abs() < 1e-6);
+        assert!((lon.rem_euclid(360.0) - point.lon.rem_euclid(360.0)).abs() < 1e-6);
+
+        assert!(matches!(
+            msg.coordinate_at(49 * 17),
+            Err(CodesError::GridpointOutOfRange { len: 833, .. })
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+use crate::{errors::CodesError, KeyRead, KeyWrite, KeyedMessage};
+
+/// Vertical level of the message, defined together by `typeOfLevel` and `level` keys.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Level {
+    /// Isobaric level (`isobaricInhPa`) with pressure in hPa
+    IsobaricHpa(f64),
+    /// Ground or water surface (`surface`)
+    Surface,
+    /// Specified height level above ground (`heightAboveGround`) in meters
+    HeightAboveGround(f64),
+    /// Any other type of level with raw values of the keys
+    Other {
+        /// Value of `typeOfLevel` key
+        type_of_level: String,
+        /// Value of `level` key
+        value: i64,
+    },
+}
+
+impl KeyedMessage {
+    /// Reads the `typeOfLevel` key, eg. `"isobaricInhPa"` or `"surface"`.
+    ///
+    /// Use [`level()`](KeyedMessage::level) to read the type together with the value of the level.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when the key cannot be read.
+    pub fn type_of_level(&self) -> Result<String, CodesError> {
+        self.read_key("typeOfLevel")
+    }
+
+    /// Reads `typeOfLevel` and `level` keys and constructs corresponding [`Level`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle, Level};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland-levels.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  assert!(matches!(message.level()?, Level::IsobaricHpa(_)));
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when any of the keys cannot be read.
+    #[allow(clippy::cast_precision_loss)] // level values are far below f64 mantissa range
+    pub fn level(&self) -> Result<Level, CodesError> {
+        let type_of_level: String = self.read_key("typeOfLevel")?;
+        let value: i64 = self.read_key("level")?;
+
+        let level = match type_of_level.as_str() {
+            "isobaricInhPa" => Level::IsobaricHpa(value as f64),
+            "surface" => Level::Surface,
+            "heightAboveGround" => Level::HeightAboveGround(value as f64),
+            _ => Level::Other {
+                type_of_level,
+                value,
+            },
+        };
+
+        Ok(level)
+    }
+
+    /// Reads hybrid level coefficients from the `pv` key as `(a, b)` pairs.
+    ///
+    /// The `pv` array contains all `a` coefficients followed by all `b` coefficients,
+    /// so its first half is zipped with the second half. The number of pairs is equal to `NV / 2`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::UnexpectedKeyValue`] when the length of `pv` array is odd.
+    ///
+    /// Returns [`CodesError`] when the key cannot be read, eg. when the message
+    /// does not contain vertical coordinate parameters.
+    pub fn hybrid_coefficients(&self) -> Result<Vec<(f64, f64)>, CodesError> {
+        let pv: Vec<f64> = self.read_key("pv")?;
+
+        if pv.len() % 2 != 0 {
+            return Err(CodesError::UnexpectedKeyValue("pv".to_owned()));
+        }
+
+        let (a, b) = pv.split_at(pv.len() / 2);
+
+        Ok(a.iter().copied().zip(b.iter().copied()).collect())
+    }
+
+    /// Writes `typeOfLevel` and `level` keys corresponding to provided [`Level`].
+    ///
+    /// For [`Level::Surface`] the `level` key is set to 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle, Level};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland-levels.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  message.set_level(&Level::IsobaricHpa(850.0))?;
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::UnexpectedKeyValue`] when the value of [`Level::IsobaricHpa`]
+    /// or [`Level::HeightAboveGround`] is not an integer, as the `level` key can store only integers.
+    /// No key is written in that case.
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal) if ecCodes fails to write any of the keys.
+    pub fn set_level(&mut self, level: &Level) -> Result<(), CodesError> {
+        match level {
+            Level::IsobaricHpa(value) => {
+                let value = integral_level(*value)?;
+                self.write_key("typeOfLevel", "isobaricInhPa")?
+                    .write_key("level", value)?;
+            }
+            Level::Surface => {
+                self.write_key("typeOfLevel", "surface")?
+                    .write_key("level", 0_i64)?;
+            }
+            Level::HeightAboveGround(value) => {
+                let value = integral_level(*value)?;
+                self.write_key("typeOfLevel", "heightAboveGround")?
+                    .write_key("level", value)?;
+            }
+            Level::Other {
+                type_of_level,
+                value,
+            } => {
+                self.write_key("typeOfLevel", type_of_level)?
+                    .write_key("level", *value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts the level value to integer, refusing values with fractional part
+/// that would be truncated when written to the `level` key.
+#[allow(clippy::cast_possible_truncation)] // value is checked to be integral
+fn integral_level(value: f64) -> Result<i64, CodesError> {
+    if !value.is_finite() || value.fract() != 0.0 {
+        return Err(CodesError::UnexpectedKeyValue("level".to_owned()));
+    }
+
+    Ok(value as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{Context, Result};
+
+    use crate::codes_handle::{CodesHandle, ProductKind};
+    use crate::intermediate_bindings::codes_handle_new_from_samples;
+    use crate::{CodesError, FallibleStreamingIterator, KeyRead, KeyWrite, KeyedMessage, Level};
+    use std::path::Path;
+
+    #[test]
+    fn read_isobaric_levels() -> Result<()> {
+        let file_path = Path::new("./data/iceland-levels.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+
+        while let Some(msg) = handle.next()? {
+            let level: i64 = msg.read_key("level")?;
+            assert_eq!(
+                msg.level()?,
+                Level::IsobaricHpa(f64::from(i32::try_from(level)?))
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_type_of_level() -> Result<()> {
+        let file_path = Path::new("./data/iceland-levels.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let msg = handle.next()?.context("Message not some")?;
+
+        assert_eq!(msg.type_of_level()?, "isobaricInhPa");
+
+        Ok(())
+    }
+
+    #[test]
+    fn write_level() -> Result<()> {
+        let file_path = Path::new("./data/iceland-levels.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.context("Message not some")?.try_clone()?;
+
+        msg.set_level(&Level::IsobaricHpa(850.0))?;
+        assert_eq!(msg.level()?, Level::IsobaricHpa(850.0));
+
+        msg.set_level(&Level::Surface)?;
+        assert_eq!(msg.level()?, Level::Surface);
+
+        let result = msg.set_level(&Level::HeightAboveGround(2.5));
+        assert!(matches!(result, Err(CodesError::UnexpectedKeyValue(_))));
+        assert_eq!(msg.level()?, Level::Surface);
+
+        Ok(())
+    }
+
+    #[test]
+    fn hybrid_coefficients_on_sample() -> Result<()> {
+        let mut msg = KeyedMessage {
+            message_handle: unsafe { codes_handle_new_from_samples("GRIB2") },
+            keys_namespace: None,
+        };
+        assert!(!msg.message_handle.is_null());
+
+        let pv = [0.0, 2000.0, 4000.0, 1.0, 0.5, 0.0];
+        msg.write_key("PVPresent", 1)?.write_key("pv", &pv[..])?;
+
+        let coefficients = msg.hybrid_coefficients()?;
+        let nv: i64 = msg.read_key("NV")?;
+
+        assert_eq!(i64::try_from(coefficients.len())?, nv / 2);
+        assert_eq!(coefficients[0], (0.0, 1.0));
+        assert_eq!(coefficients[2], (4000.0, 0.0));
+
+        Ok(())
+    }
+}
@@ -1,17 +1,35 @@
 //! Definition of `KeyedMessage` and its associated functions
 //! used for reading and writing data of given variable from GRIB file
 
+mod bufr;
+mod grid;
+mod level;
+mod product;
 mod read;
+mod regrid;
+#[cfg(feature = "chrono")]
+mod time;
+mod units;
 mod write;
 
+pub use grid::{GridDefinition, GridType};
+pub use level::Level;
+pub use product::{ProcessedDataType, ProductionStatus};
+pub use regrid::Interpolation;
+
 use eccodes_sys::codes_handle;
 use log::error;
-use std::ptr::null_mut;
+use std::{
+    fmt::{self, Display},
+    ptr::null_mut,
+    slice,
+};
 
 use crate::{
+    errors::CodesInternal,
     intermediate_bindings::{
-        codes_get_native_type, codes_get_size, codes_handle_clone, codes_handle_delete,
-        NativeKeyType,
+        codes_get_message, codes_get_native_type, codes_get_size, codes_handle_clone,
+        codes_handle_delete,
     },
     CodesError,
 };
@@ -49,8 +67,24 @@ use crate::{
 #[derive(Hash, Debug)]
 pub struct KeyedMessage {
     pub(crate) message_handle: *mut codes_handle,
+    /// Namespace used by [`default_keys_iterator()`](KeyedMessage::default_keys_iterator)
+    pub(crate) keys_namespace: Option<String>,
 }
 
+// SAFETY: moving a `KeyedMessage` to another thread moves the exclusive ownership of its handle.
+// - Every handle is created by `codes_handle_new_from_file`, `codes_handle_new_from_index`,
+//   `codes_handle_new_from_samples` or `codes_handle_clone`, which all copy the message data
+//   into a buffer owned by the handle, so the handle does not point into the source or other messages.
+// - ecCodes keeps no thread-local state for handles. Handle memory is allocated and freed
+//   with the memory procedures of the default context (`malloc`/`free`), so `codes_handle_delete`
+//   in `Drop` can run on a different thread than the one that created the handle.
+// - The default context is shared between handles, and ecCodes guards it with its own mutex.
+// - `KeysIterator` and `CodesNearest` hold raw pointers tied to the handle, but they borrow
+//   the message and are themselves neither `Send` nor `Sync`, so the message cannot be moved
+//   to another thread while they exist.
+// Sync is not implemented, as ecCodes mutates the handle when reading keys.
+unsafe impl Send for KeyedMessage {}
+
 /// Provides GRIB key reading capabilites. Implemented by [`KeyedMessage`] for all possible key types.
 pub trait KeyRead<T> {
     /// Tries to read a key of given name from [`KeyedMessage`]. This function checks if key native type
@@ -79,7 +113,8 @@ pub trait KeyRead<T> {
     ///
     /// # Errors
     ///
-    /// Returns [`WrongRequestedKeySize`](CodesError::WrongRequestedKeyType) when trying to read key in non-native type (use [`unchecked`](KeyRead::read_key_unchecked) instead).
+    /// Returns [`WrongRequestedKeyType`](CodesError::WrongRequestedKeyType) with the native type of the key
+    /// when trying to read key in non-native type (use [`unchecked`](KeyRead::read_key_unchecked) instead).
     ///
     /// Returns [`WrongRequestedKeySize`](CodesError::WrongRequestedKeySize) when trying to read array as integer.
     ///
@@ -145,6 +180,11 @@ pub trait KeyWrite<T> {
     /// // To edit a message it must be cloned.
     ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
     ///  message.write_key("level", 1)?;
+    ///
+    ///  // Writes can be chained
+    ///  message
+    ///      .write_key("level", 850)?
+    ///      .write_key("centre", "cnmc")?;
     ///  # Ok(())
     ///  # }
     /// ```
@@ -152,7 +192,7 @@ pub trait KeyWrite<T> {
     /// # Errors
     ///
     /// This function will return [`CodesInternal`](crate::errors::CodesInternal) if ecCodes fails to write the key.
-    fn write_key(&mut self, name: &str, value: T) -> Result<(), CodesError>;
+    fn write_key(&mut self, name: &str, value: T) -> Result<&mut Self, CodesError>;
 }
 
 /// Enum of types GRIB key can have.
@@ -176,16 +216,79 @@ pub enum DynamicKeyType {
     Bytes(Vec<u8>),
 }
 
+/// Number of array elements printed by [`DynamicKeyType`] `Display` implementation
+const DISPLAYED_ELEMENTS: usize = 5;
+
+/// Renders the value plainly (eg. `msl`, `850`). Arrays longer than 5 elements
+/// are truncated and their length is appended (eg. `[1, 2, 3, 4, 5, ...] (1000 elements)`).
+impl Display for DynamicKeyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DynamicKeyType::Float(value) => write!(f, "{value}"),
+            DynamicKeyType::Int(value) => write!(f, "{value}"),
+            DynamicKeyType::Str(value) => write!(f, "{value}"),
+            DynamicKeyType::FloatArray(values) => fmt_array(f, values),
+            DynamicKeyType::IntArray(values) => fmt_array(f, values),
+            DynamicKeyType::Bytes(values) => fmt_array(f, values),
+        }
+    }
+}
+
+fn fmt_array<T: Display>(f: &mut fmt::Formatter<'_>, values: &[T]) -> fmt::Result {
+    write!(f, "[")?;
+
+    for (i, value) in values.iter().take(DISPLAYED_ELEMENTS).enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{value}")?;
+    }
+
+    if values.len() > DISPLAYED_ELEMENTS {
+        write!(f, ", ...] ({} elements)", values.len())
+    } else {
+        write!(f, "]")
+    }
+}
+
+/// Enum of native types of GRIB keys as reported by ecCodes.
+///
+/// Native type determines which type a key can be read as with [`read_key()`](KeyRead::read_key).
+/// Keys of `Long` and `Double` type can be either scalars or arrays.
+#[derive(Copy, Eq, PartialEq, Clone, Ord, PartialOrd, Hash, Debug, num_derive::FromPrimitive)]
+pub enum NativeKeyType {
+    #[allow(missing_docs)]
+    Undefined = eccodes_sys::CODES_TYPE_UNDEFINED as isize,
+    #[allow(missing_docs)]
+    Long = eccodes_sys::CODES_TYPE_LONG as isize,
+    #[allow(missing_docs)]
+    Double = eccodes_sys::CODES_TYPE_DOUBLE as isize,
+    #[allow(missing_docs)]
+    Str = eccodes_sys::CODES_TYPE_STRING as isize,
+    #[allow(missing_docs)]
+    Bytes = eccodes_sys::CODES_TYPE_BYTES as isize,
+    #[allow(missing_docs)]
+    Section = eccodes_sys::CODES_TYPE_SECTION as isize,
+    #[allow(missing_docs)]
+    Label = eccodes_sys::CODES_TYPE_LABEL as isize,
+    #[allow(missing_docs)]
+    Missing = eccodes_sys::CODES_TYPE_MISSING as isize,
+}
+
 impl KeyedMessage {
     /// Custom function to clone the `KeyedMessage`. This function comes with memory overhead.
     ///
     /// # Errors
-    /// This function will return [`CodesInternal`](crate::errors::CodesInternal) if ecCodes fails to clone the message.
+    /// This function will return [`CodesError::CloneFailed`] if ecCodes fails to clone the message
+    /// (returns a null pointer), so a message with null handle is never constructed.
+    ///
+    /// Returns [`CodesError::NullPtr`] if the handle of the source message is null.
     pub fn try_clone(&self) -> Result<Self, CodesError> {
         let new_handle = unsafe { codes_handle_clone(self.message_handle)? };
 
         Ok(Self {
             message_handle: new_handle,
+            keys_namespace: self.keys_namespace.clone(),
         })
     }
 
@@ -196,6 +299,52 @@ impl KeyedMessage {
     fn get_key_native_type(&self, key_name: &str) -> Result<NativeKeyType, CodesError> {
         unsafe { codes_get_native_type(self.message_handle, key_name) }
     }
+
+    /// Same as [`read_key_dynamic()`](KeyedMessage::read_key_dynamic) but returns `None`
+    /// instead of an error when the key is not present in the message.
+    pub(crate) fn read_key_dynamic_if_present(
+        &self,
+        key_name: &str,
+    ) -> Result<Option<DynamicKeyType>, CodesError> {
+        match self.read_key_dynamic(key_name) {
+            Ok(value) => Ok(Some(value)),
+            Err(CodesError::Internal(CodesInternal::CodesNotFound) | CodesError::MissingKey) => {
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Encodes the message into GRIB bytes, eg. to send it through a channel
+/// or pass it to APIs expecting raw messages.
+///
+/// # Example
+///
+/// ```
+///  use eccodes::{ProductKind, CodesHandle};
+///  # use std::path::Path;
+///  # use anyhow::Context;
+///  use eccodes::FallibleStreamingIterator;
+///  #
+///  # fn main() -> anyhow::Result<()> {
+///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+///  let message = handle.next()?.context("no message")?;
+///
+///  let bytes: Vec<u8> = message.try_into()?;
+///  assert_eq!(&bytes[..4], b"GRIB");
+///  # Ok(())
+///  # }
+/// ```
+impl TryFrom<&KeyedMessage> for Vec<u8> {
+    type Error = CodesError;
+
+    fn try_from(message: &KeyedMessage) -> Result<Self, Self::Error> {
+        let (buffer, length) = unsafe { codes_get_message(message.message_handle)? };
+        let bytes = unsafe { slice::from_raw_parts(buffer.cast::<u8>(), length) };
+
+        Ok(bytes.to_vec())
+    }
 }
 
 #[doc(hidden)]
@@ -227,9 +376,10 @@ impl Drop for KeyedMessage {
 #[cfg(test)]
 mod tests {
     use crate::codes_handle::{CodesHandle, ProductKind};
-    use crate::FallibleStreamingIterator;
+    use crate::intermediate_bindings::codes_handle_clone;
+    use crate::{CodesError, DynamicKeyType, FallibleStreamingIterator};
     use anyhow::{Context, Result};
-    use std::path::Path;
+    use std::{path::Path, ptr::null_mut};
     use testing_logger;
 
     #[test]
@@ -251,6 +401,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn dynamic_key_display() {
+        assert_eq!(format!("{}", DynamicKeyType::Int(850)), "850");
+        assert_eq!(format!("{}", DynamicKeyType::Float(1.5)), "1.5");
+        assert_eq!(format!("{}", DynamicKeyType::Str("msl".to_string())), "msl");
+        assert_eq!(
+            format!("{}", DynamicKeyType::IntArray(vec![1, 2, 3])),
+            "[1, 2, 3]"
+        );
+        assert_eq!(
+            format!("{}", DynamicKeyType::FloatArray(vec![0.5; 1000])),
+            "[0.5, 0.5, 0.5, 0.5, 0.5, ...] (1000 elements)"
+        );
+        assert_eq!(format!("{}", DynamicKeyType::Bytes(vec![])), "[]");
+    }
+
     #[test]
     fn message_clone_1() -> Result<()> {
         let file_path = Path::new("./data/iceland.grib");
@@ -289,6 +455,13 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn clone_null_handle() {
+        let clone = unsafe { codes_handle_clone(null_mut()) };
+
+        assert!(matches!(clone, Err(CodesError::NullPtr)));
+    }
+
     #[test]
     fn message_clone_drop() -> Result<()> {
         testing_logger::setup();
@@ -321,4 +494,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn message_into_bytes() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        let bytes: Vec<u8> = current_message.try_into()?;
+        assert_eq!(bytes.len(), current_message.encoded_size()?);
+
+        let mut reparsed_handle = CodesHandle::new_from_memory(bytes, product_kind)?;
+        let reparsed_message = reparsed_handle.next()?.context("Message not some")?;
+
+        assert_eq!(current_message.data_hash()?, reparsed_message.data_hash()?);
+        assert_eq!(
+            current_message.read_key_dynamic("shortName")?,
+            reparsed_message.read_key_dynamic("shortName")?
+        );
+        assert!(reparsed_handle.next()?.is_none());
+
+        Ok(())
+    }
 }
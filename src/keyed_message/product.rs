@@ -0,0 +1,215 @@
+use crate::{errors::CodesError, KeyRead, KeyWrite, KeyedMessage};
+
+/// Production status of the data in GRIB2 message, as indicated by
+/// the `productionStatusOfProcessedData` key (WMO Code table 1.3).
+///
+/// Codes not listed here are returned as [`ProductionStatus::Other`] with the raw key value.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ProductionStatus {
+    /// Operational products (code 0)
+    Operational,
+    /// Operational test products (code 1)
+    OperationalTest,
+    /// Research products (code 2)
+    Research,
+    /// Re-analysis products (code 3)
+    Reanalysis,
+    /// Any other code
+    Other(i64),
+}
+
+/// Type of data in GRIB2 message, as indicated by
+/// the `typeOfProcessedData` key (WMO Code table 1.4).
+///
+/// Codes not listed here are returned as [`ProcessedDataType::Other`] with the raw key value.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ProcessedDataType {
+    /// Analysis products (code 0)
+    Analysis,
+    /// Forecast products (code 1)
+    Forecast,
+    /// Analysis and forecast products (code 2)
+    AnalysisAndForecast,
+    /// Control forecast products (code 3)
+    ControlForecast,
+    /// Perturbed forecast products (code 4)
+    PerturbedForecast,
+    /// Control and perturbed forecast products (code 5)
+    ControlAndPerturbedForecast,
+    /// Processed satellite observations (code 6)
+    SatelliteObservations,
+    /// Processed radar observations (code 7)
+    RadarObservations,
+    /// Event probability (code 8)
+    EventProbability,
+    /// Any other code
+    Other(i64),
+}
+
+impl From<i64> for ProductionStatus {
+    fn from(value: i64) -> Self {
+        match value {
+            0 => ProductionStatus::Operational,
+            1 => ProductionStatus::OperationalTest,
+            2 => ProductionStatus::Research,
+            3 => ProductionStatus::Reanalysis,
+            _ => ProductionStatus::Other(value),
+        }
+    }
+}
+
+impl From<ProductionStatus> for i64 {
+    fn from(value: ProductionStatus) -> Self {
+        match value {
+            ProductionStatus::Operational => 0,
+            ProductionStatus::OperationalTest => 1,
+            ProductionStatus::Research => 2,
+            ProductionStatus::Reanalysis => 3,
+            ProductionStatus::Other(code) => code,
+        }
+    }
+}
+
+impl From<i64> for ProcessedDataType {
+    fn from(value: i64) -> Self {
+        match value {
+            0 => ProcessedDataType::Analysis,
+            1 => ProcessedDataType::Forecast,
+            2 => ProcessedDataType::AnalysisAndForecast,
+            3 => ProcessedDataType::ControlForecast,
+            4 => ProcessedDataType::PerturbedForecast,
+            5 => ProcessedDataType::ControlAndPerturbedForecast,
+            6 => ProcessedDataType::SatelliteObservations,
+            7 => ProcessedDataType::RadarObservations,
+            8 => ProcessedDataType::EventProbability,
+            _ => ProcessedDataType::Other(value),
+        }
+    }
+}
+
+impl From<ProcessedDataType> for i64 {
+    fn from(value: ProcessedDataType) -> Self {
+        match value {
+            ProcessedDataType::Analysis => 0,
+            ProcessedDataType::Forecast => 1,
+            ProcessedDataType::AnalysisAndForecast => 2,
+            ProcessedDataType::ControlForecast => 3,
+            ProcessedDataType::PerturbedForecast => 4,
+            ProcessedDataType::ControlAndPerturbedForecast => 5,
+            ProcessedDataType::SatelliteObservations => 6,
+            ProcessedDataType::RadarObservations => 7,
+            ProcessedDataType::EventProbability => 8,
+            ProcessedDataType::Other(code) => code,
+        }
+    }
+}
+
+impl KeyedMessage {
+    /// Reads the `productionStatusOfProcessedData` key and maps it to [`ProductionStatus`].
+    ///
+    /// The key is present only in GRIB2 messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when the key is not present or cannot be read as an integer.
+    pub fn production_status(&self) -> Result<ProductionStatus, CodesError> {
+        let code: i64 = self.read_key("productionStatusOfProcessedData")?;
+
+        Ok(ProductionStatus::from(code))
+    }
+
+    /// Writes the code of [`ProductionStatus`] to the `productionStatusOfProcessedData` key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{CodesHandle, KeyRead, ProductKind, ProductionStatus};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/gfs.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  message.set_production_status(ProductionStatus::Research)?;
+    ///
+    ///  let code: i64 = message.read_key("productionStatusOfProcessedData")?;
+    ///  assert_eq!(code, 2);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when the key cannot be written, eg. in GRIB1 messages.
+    pub fn set_production_status(&mut self, status: ProductionStatus) -> Result<(), CodesError> {
+        self.write_key("productionStatusOfProcessedData", i64::from(status))?;
+
+        Ok(())
+    }
+
+    /// Reads the `typeOfProcessedData` key and maps it to [`ProcessedDataType`].
+    ///
+    /// The key is present only in GRIB2 messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when the key is not present or cannot be read as an integer.
+    pub fn processed_data_type(&self) -> Result<ProcessedDataType, CodesError> {
+        let code: i64 = self.read_key("typeOfProcessedData")?;
+
+        Ok(ProcessedDataType::from(code))
+    }
+
+    /// Writes the code of [`ProcessedDataType`] to the `typeOfProcessedData` key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when the key cannot be written, eg. in GRIB1 messages.
+    pub fn set_processed_data_type(
+        &mut self,
+        data_type: ProcessedDataType,
+    ) -> Result<(), CodesError> {
+        self.write_key("typeOfProcessedData", i64::from(data_type))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+
+    use crate::intermediate_bindings::codes_handle_new_from_samples;
+    use crate::{KeyRead, KeyedMessage, ProcessedDataType, ProductionStatus};
+
+    #[test]
+    fn production_status_and_data_type() -> Result<()> {
+        let mut msg = KeyedMessage {
+            message_handle: unsafe { codes_handle_new_from_samples("GRIB2") },
+            keys_namespace: None,
+        };
+        assert!(!msg.message_handle.is_null());
+
+        msg.set_production_status(ProductionStatus::Reanalysis)?;
+        msg.set_processed_data_type(ProcessedDataType::PerturbedForecast)?;
+
+        let status: i64 = msg.read_key("productionStatusOfProcessedData")?;
+        let data_type: i64 = msg.read_key("typeOfProcessedData")?;
+        assert_eq!((status, data_type), (3, 4));
+
+        assert_eq!(msg.production_status()?, ProductionStatus::Reanalysis);
+        assert_eq!(
+            msg.processed_data_type()?,
+            ProcessedDataType::PerturbedForecast
+        );
+
+        msg.set_production_status(ProductionStatus::Other(6))?;
+        assert_eq!(msg.production_status()?, ProductionStatus::Other(6));
+
+        Ok(())
+    }
+}
@@ -1,20 +1,31 @@
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    ops::Range,
+    slice,
+};
 
 use crate::{
     errors::CodesError,
     intermediate_bindings::{
-        codes_get_bytes, codes_get_double, codes_get_double_array, codes_get_long,
-        codes_get_long_array, codes_get_native_type, codes_get_size, codes_get_string,
-        NativeKeyType,
+        codes_get_bytes, codes_get_double, codes_get_double_array, codes_get_double_array_into,
+        codes_get_long, codes_get_long_array, codes_get_message, codes_get_message_size,
+        codes_get_native_type, codes_get_size, codes_get_string,
     },
-    DynamicKeyType, KeyRead, KeyedMessage,
+    DynamicKeyType, KeyRead, KeyedMessage, NativeKeyType,
 };
 
 impl KeyRead<i64> for KeyedMessage {
     fn read_key(&self, key_name: &str) -> Result<i64, CodesError> {
         match self.get_key_native_type(key_name)? {
             NativeKeyType::Long => (),
-            _ => return Err(CodesError::WrongRequestedKeyType),
+            other => {
+                return Err(CodesError::WrongRequestedKeyType {
+                    requested: "i64",
+                    actual: other,
+                })
+            }
         }
 
         let key_size = self.get_key_size(key_name)?;
@@ -33,11 +44,47 @@ impl KeyRead<i64> for KeyedMessage {
     }
 }
 
+/// Reads `Long` keys with `0`/`1` values (eg. `bitmapPresent`, `iScansNegatively`) as `bool`.
+impl KeyRead<bool> for KeyedMessage {
+    fn read_key(&self, key_name: &str) -> Result<bool, CodesError> {
+        let value: i64 = self.read_key(key_name)?;
+
+        long_to_bool(key_name, value)
+    }
+
+    fn read_key_unchecked(&self, key_name: &str) -> Result<bool, CodesError> {
+        let value: i64 = self.read_key_unchecked(key_name)?;
+
+        long_to_bool(key_name, value)
+    }
+}
+
+fn long_to_bool(key_name: &str, value: i64) -> Result<bool, CodesError> {
+    match value {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(CodesError::UnexpectedKeyValue(key_name.to_owned())),
+    }
+}
+
+/// Returns the range of section bytes, or `None` when the section does not fit in the message
+/// (eg. because offset or length keys of a corrupted message overflow).
+fn section_range(offset: usize, length: usize, message_len: usize) -> Option<Range<usize>> {
+    let end = offset.checked_add(length)?;
+
+    (end <= message_len).then_some(offset..end)
+}
+
 impl KeyRead<f64> for KeyedMessage {
     fn read_key(&self, key_name: &str) -> Result<f64, CodesError> {
         match self.get_key_native_type(key_name)? {
             NativeKeyType::Double => (),
-            _ => return Err(CodesError::WrongRequestedKeyType),
+            other => {
+                return Err(CodesError::WrongRequestedKeyType {
+                    requested: "f64",
+                    actual: other,
+                })
+            }
         }
 
         let key_size = self.get_key_size(key_name)?;
@@ -60,7 +107,12 @@ impl KeyRead<String> for KeyedMessage {
     fn read_key(&self, key_name: &str) -> Result<String, CodesError> {
         match self.get_key_native_type(key_name)? {
             NativeKeyType::Str => (),
-            _ => return Err(CodesError::WrongRequestedKeyType),
+            other => {
+                return Err(CodesError::WrongRequestedKeyType {
+                    requested: "String",
+                    actual: other,
+                })
+            }
         }
 
         let key_size = self.get_key_size(key_name)?;
@@ -81,7 +133,12 @@ impl KeyRead<Vec<i64>> for KeyedMessage {
     fn read_key(&self, key_name: &str) -> Result<Vec<i64>, CodesError> {
         match self.get_key_native_type(key_name)? {
             NativeKeyType::Long => (),
-            _ => return Err(CodesError::WrongRequestedKeyType),
+            other => {
+                return Err(CodesError::WrongRequestedKeyType {
+                    requested: "Vec<i64>",
+                    actual: other,
+                })
+            }
         }
 
         let key_size = self.get_key_size(key_name)?;
@@ -102,7 +159,12 @@ impl KeyRead<Vec<f64>> for KeyedMessage {
     fn read_key(&self, key_name: &str) -> Result<Vec<f64>, CodesError> {
         match self.get_key_native_type(key_name)? {
             NativeKeyType::Double => (),
-            _ => return Err(CodesError::WrongRequestedKeyType),
+            other => {
+                return Err(CodesError::WrongRequestedKeyType {
+                    requested: "Vec<f64>",
+                    actual: other,
+                })
+            }
         }
 
         let key_size = self.get_key_size(key_name)?;
@@ -123,7 +185,12 @@ impl KeyRead<Vec<u8>> for KeyedMessage {
     fn read_key(&self, key_name: &str) -> Result<Vec<u8>, CodesError> {
         match self.get_key_native_type(key_name)? {
             NativeKeyType::Bytes => (),
-            _ => return Err(CodesError::WrongRequestedKeyType),
+            other => {
+                return Err(CodesError::WrongRequestedKeyType {
+                    requested: "Vec<u8>",
+                    actual: other,
+                })
+            }
         }
 
         let key_size = self.get_key_size(key_name)?;
@@ -188,7 +255,7 @@ impl KeyedMessage {
     ///
     /// Returns [`CodesError::Internal`] when one of internal ecCodes functions to read the key fails.
     ///
-    /// Returns [`CodesError::CstrUTF8`] and [`CodesError::NulChar`] when the string returned by ecCodes
+    /// Returns [`CodesError::CstrUTF8`] when the string returned by ecCodes
     /// library cannot be parsed as valid UTF8 Rust string.
     ///
     /// Returns [`CodesError::IncorrectKeySize`] when the size of given key is lower than 1. This indicates corrupted data file,
@@ -294,6 +361,475 @@ impl KeyedMessage {
             Ok(DynamicKeyType::Bytes(value))
         }
     }
+
+    /// Reads a `Double` array key of known length into a fixed-size array,
+    /// avoiding heap allocation for small arrays.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let [increment] = message.read_key_array::<1>("jDirectionIncrementInDegrees")?;
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::WrongRequestedKeyType`] when the key is not of `Double` type.
+    ///
+    /// Returns [`CodesError::WrongRequestedKeySize`] when the size of the key is not equal to `N`.
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when internal ecCodes function returns non-zero code.
+    pub fn read_key_array<const N: usize>(&self, key_name: &str) -> Result<[f64; N], CodesError> {
+        match self.get_key_native_type(key_name)? {
+            NativeKeyType::Double => (),
+            other => {
+                return Err(CodesError::WrongRequestedKeyType {
+                    requested: "[f64; N]",
+                    actual: other,
+                })
+            }
+        }
+
+        if self.get_key_size(key_name)? != N {
+            return Err(CodesError::WrongRequestedKeySize);
+        }
+
+        let mut values = [0.0; N];
+
+        unsafe {
+            codes_get_double_array_into(self.message_handle, key_name, &mut values)?;
+        }
+
+        Ok(values)
+    }
+
+    /// Reads the `shortName` key, the most common way of identifying the parameter of the message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  if message.short_name()? == "msl" {
+    ///      println!("found mean sea level pressure");
+    ///  }
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when the key cannot be read.
+    pub fn short_name(&self) -> Result<String, CodesError> {
+        self.read_key("shortName")
+    }
+
+    /// Reads multiple keys with [`read_key_dynamic()`](KeyedMessage::read_key_dynamic)
+    /// and returns results for each key in the same order as provided names.
+    ///
+    /// Failure to read one key does not affect reading other keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle, DynamicKeyType};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let keys = message.read_keys(&["shortName", "typeOfLevel"]);
+    ///  assert_eq!(keys[0].as_ref().ok(), Some(&DynamicKeyType::Str("msl".to_string())));
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    #[must_use]
+    pub fn read_keys(&self, key_names: &[&str]) -> Vec<Result<DynamicKeyType, CodesError>> {
+        key_names
+            .iter()
+            .map(|key_name| self.read_key_dynamic(key_name))
+            .collect()
+    }
+
+    /// Returns native types of multiple keys in the same order as provided names,
+    /// eg. to decide how each key should be read with [`read_keys()`](KeyedMessage::read_keys).
+    ///
+    /// Failure to get the type of one key does not affect other keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle, NativeKeyType};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let types = message.native_types(&["shortName", "level"]);
+    ///  assert_eq!(types[0].as_ref().ok(), Some(&NativeKeyType::Str));
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    #[must_use]
+    pub fn native_types(&self, key_names: &[&str]) -> Vec<Result<NativeKeyType, CodesError>> {
+        key_names
+            .iter()
+            .map(|key_name| self.get_key_native_type(key_name))
+            .collect()
+    }
+
+    /// Reads provided keys with [`read_key_dynamic()`](KeyedMessage::read_key_dynamic)
+    /// and joins their [`Display`](std::fmt::Display) representations with `separator`,
+    /// producing a single row for tabular (eg. CSV) export.
+    ///
+    /// Keys that are not present in the message are rendered as empty fields.
+    /// Values are not quoted or escaped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let row = message.to_csv_row(&["shortName", "typeOfLevel"], ',')?;
+    ///  assert_eq!(row, "msl,surface");
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when internal ecCodes function returns non-zero code
+    /// for reasons other than the key not being present.
+    pub fn to_csv_row(&self, key_names: &[&str], separator: char) -> Result<String, CodesError> {
+        let mut row = String::new();
+
+        for (i, key_name) in key_names.iter().enumerate() {
+            if i > 0 {
+                row.push(separator);
+            }
+
+            if let Some(value) = self.read_key_dynamic_if_present(key_name)? {
+                row.push_str(&value.to_string());
+            }
+        }
+
+        Ok(row)
+    }
+
+    /// Returns raw bytes of the GRIB section with given number, eg. `1` for the identification section.
+    ///
+    /// Section boundaries are read from `offsetSection<N>` and `section<N>Length` keys
+    /// and the bytes are copied out of the encoded message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let section1 = message.section_bytes(1)?;
+    ///  assert!(!section1.is_empty());
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal) when the section does not exist
+    /// in the message or internal ecCodes function returns non-zero code.
+    ///
+    /// Returns [`CodesError::UnexpectedKeyValue`] when the section offset or length
+    /// is outside of the encoded message.
+    pub fn section_bytes(&self, section: u8) -> Result<Vec<u8>, CodesError> {
+        let offset_key = format!("offsetSection{section}");
+        let length_key = format!("section{section}Length");
+
+        let offset: i64 = self.read_key(&offset_key)?;
+        let offset =
+            usize::try_from(offset).map_err(|_| CodesError::UnexpectedKeyValue(offset_key))?;
+
+        let length: i64 = self.read_key(&length_key)?;
+        let length = usize::try_from(length)
+            .map_err(|_| CodesError::UnexpectedKeyValue(length_key.clone()))?;
+
+        let msg = unsafe { codes_get_message(self.message_handle)? };
+        let range = section_range(offset, length, msg.1)
+            .ok_or(CodesError::UnexpectedKeyValue(length_key))?;
+
+        let buf = unsafe { slice::from_raw_parts(msg.0.cast::<u8>(), msg.1) };
+
+        Ok(buf[range].to_vec())
+    }
+
+    /// Computes a hash of the encoded message bytes, which can be used
+    /// to detect duplicate messages by their content.
+    ///
+    /// The hash is computed with [`DefaultHasher`], so it is not guaranteed to be
+    /// the same between Rust releases and should not be persisted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  assert_eq!(message.data_hash()?, message.try_clone()?.data_hash()?);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when internal ecCodes function returns non-zero code.
+    pub fn data_hash(&self) -> Result<u64, CodesError> {
+        let msg = unsafe { codes_get_message(self.message_handle)? };
+        let buf = unsafe { slice::from_raw_parts(msg.0.cast::<u8>(), msg.1) };
+
+        let mut hasher = DefaultHasher::new();
+        buf.hash(&mut hasher);
+
+        Ok(hasher.finish())
+    }
+
+    /// Returns a human-readable logical identity of the message in the form
+    /// `shortName/typeOfLevel/level/validityDateTvalidityTime/stepstepUnits`, eg. `2t/surface/0/20210601T0000/0h`.
+    ///
+    /// Unlike [`data_hash()`](KeyedMessage::data_hash), the identity does not depend on data values
+    /// or encoding, so it can be used to group or deduplicate messages with the same metadata across files.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  assert!(message.identity_key()?.starts_with("msl/"));
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when any of the keys cannot be read.
+    pub fn identity_key(&self) -> Result<String, CodesError> {
+        let short_name = self.short_name()?;
+        let type_of_level = self.type_of_level()?;
+        let level: i64 = self.read_key_unchecked("level")?;
+        let validity_date: i64 = self.read_key_unchecked("validityDate")?;
+        let validity_time: i64 = self.read_key_unchecked("validityTime")?;
+        let step: i64 = self.read_key_unchecked("step")?;
+        let step_units: String = self.read_key_unchecked("stepUnits")?;
+
+        Ok(format!(
+            "{short_name}/{type_of_level}/{level}/{validity_date}T{validity_time:04}/{step}{step_units}"
+        ))
+    }
+
+    /// Returns the length in bytes of the encoded message without copying it.
+    ///
+    /// Can be used to preallocate buffers before writing the message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let mut buffer: Vec<u8> = Vec::with_capacity(message.encoded_size()?);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when internal ecCodes function returns non-zero code.
+    pub fn encoded_size(&self) -> Result<usize, CodesError> {
+        unsafe { codes_get_message_size(self.message_handle) }
+    }
+
+    /// Returns the number of bytes taken by the data values decoded as `f64`,
+    /// ie. `numberOfValues * 8`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when the `numberOfValues` key cannot be read.
+    ///
+    /// Returns [`CodesError::UnexpectedKeyValue`] when `numberOfValues` is negative.
+    pub fn decoded_values_bytes(&self) -> Result<usize, CodesError> {
+        let number_of_values: i64 = self.read_key("numberOfValues")?;
+        let number_of_values = usize::try_from(number_of_values)
+            .map_err(|_| CodesError::UnexpectedKeyValue("numberOfValues".to_owned()))?;
+
+        Ok(number_of_values * size_of::<f64>())
+    }
+
+    /// Returns an estimate of memory in bytes needed to clone the message and read its values,
+    /// ie. the sum of [`encoded_size()`](KeyedMessage::encoded_size)
+    /// and [`decoded_values_bytes()`](KeyedMessage::decoded_values_bytes).
+    ///
+    /// The estimate does not include the internal structures of ecCodes, so the actual usage is higher,
+    /// but it can be used to check that there is enough memory before collecting many messages.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland-levels.grib"), ProductKind::GRIB)?;
+    ///
+    ///  let mut total = 0;
+    ///  while let Some(message) = handle.next()? {
+    ///      total += message.estimated_clone_cost()?;
+    ///  }
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`encoded_size()`](KeyedMessage::encoded_size)
+    /// and [`decoded_values_bytes()`](KeyedMessage::decoded_values_bytes).
+    pub fn estimated_clone_cost(&self) -> Result<usize, CodesError> {
+        Ok(self.encoded_size()? + self.decoded_values_bytes()?)
+    }
+
+    /// Returns an iterator over data values of the message, convenient for reductions
+    /// like `sum()` or `fold()`.
+    ///
+    /// ecCodes decodes all values at once, so the iterator owns the whole array
+    /// and does not borrow the message.
+    ///
+    /// When `skip_missing` is `true` and the message contains a bitmap,
+    /// values equal to `missingValue` are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let max = message
+    ///      .values_iter(true)?
+    ///      .fold(f64::NEG_INFINITY, f64::max);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when `values`, `bitmapPresent` or `missingValue` keys cannot be read.
+    #[allow(clippy::float_cmp)] // missing values are set by ecCodes to exactly `missingValue`
+    pub fn values_iter(&self, skip_missing: bool) -> Result<impl Iterator<Item = f64>, CodesError> {
+        let values: Vec<f64> = self.read_key("values")?;
+
+        let bitmap_present = skip_missing && KeyRead::<bool>::read_key(self, "bitmapPresent")?;
+        let missing_value: Option<f64> = if bitmap_present {
+            Some(self.read_key_unchecked("missingValue")?)
+        } else {
+            None
+        };
+
+        Ok(values
+            .into_iter()
+            .filter(move |value| missing_value.is_none_or(|missing| *value != missing)))
+    }
+
+    /// Reads the key as `f64` regardless of its native type, letting ecCodes convert the value.
+    ///
+    /// Useful for keys with unexpected native types, eg. numeric keys stored as strings
+    /// (like `experimentVersionNumber`). Same as calling
+    /// [`read_key_unchecked`](KeyRead::read_key_unchecked) with `f64` type.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when the key is not present or ecCodes cannot convert its value to `f64`.
+    pub fn force_double(&self, key_name: &str) -> Result<f64, CodesError> {
+        unsafe { codes_get_double(self.message_handle, key_name) }
+    }
+
+    /// Reads the key as `i64` regardless of its native type, letting ecCodes convert the value.
+    ///
+    /// Floating-point values are truncated by ecCodes. Same as calling
+    /// [`read_key_unchecked`](KeyRead::read_key_unchecked) with `i64` type.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when the key is not present or ecCodes cannot convert its value to `i64`.
+    pub fn force_long(&self, key_name: &str) -> Result<i64, CodesError> {
+        unsafe { codes_get_long(self.message_handle, key_name) }
+    }
 }
 
 #[cfg(test)]
@@ -301,7 +837,12 @@ mod tests {
     use anyhow::{Context, Result};
 
     use crate::codes_handle::{CodesHandle, ProductKind};
-    use crate::{DynamicKeyType, FallibleIterator, FallibleStreamingIterator};
+    use crate::intermediate_bindings::codes_get_message;
+    use crate::keyed_message::read::section_range;
+    use crate::{
+        CodesError, DynamicKeyType, FallibleIterator, FallibleStreamingIterator, KeyRead, KeyWrite,
+        NativeKeyType,
+    };
     use std::path::Path;
 
     #[test]
@@ -377,6 +918,332 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn wrong_requested_type() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        let result: Result<i64, CodesError> =
+            current_message.read_key("jDirectionIncrementInDegrees");
+
+        match result {
+            Err(err @ CodesError::WrongRequestedKeyType { requested, actual }) => {
+                assert_eq!(requested, "i64");
+                assert_eq!(actual, NativeKeyType::Double);
+                assert!(err.to_string().contains("requested i64 but key is Double"));
+            }
+            _ => panic!("Expected WrongRequestedKeyType error"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_multiple_keys() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        let keys = current_message.read_keys(&["shortName", "level", "doesNotExist"]);
+
+        assert_eq!(keys.len(), 3);
+        assert_eq!(
+            keys[0].as_ref().ok(),
+            Some(&DynamicKeyType::Str("msl".into()))
+        );
+        assert!(matches!(keys[1], Ok(DynamicKeyType::Int(_))));
+        assert!(keys[2].is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_short_name() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        assert_eq!(current_message.short_name()?, "msl");
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_multiple_native_types() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        let types = current_message.native_types(&["values", "shortName", "level"]);
+
+        assert_eq!(types.len(), 3);
+        assert_eq!(types[0].as_ref().ok(), Some(&NativeKeyType::Double));
+        assert_eq!(types[1].as_ref().ok(), Some(&NativeKeyType::Str));
+        assert_eq!(types[2].as_ref().ok(), Some(&NativeKeyType::Long));
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_bool_key() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut current_message = handle.next()?.context("Message not some")?.try_clone()?;
+
+        let bitmap_present: bool = current_message.read_key("bitmapPresent")?;
+        assert!(!bitmap_present);
+
+        current_message.write_key("bitmapPresent", 1_i64)?;
+        let bitmap_present: bool = current_message.read_key("bitmapPresent")?;
+        assert!(bitmap_present);
+
+        let not_bool: Result<bool, CodesError> = current_message.read_key("Ni");
+        assert!(matches!(not_bool, Err(CodesError::UnexpectedKeyValue(key)) if key == "Ni"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_bytes() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        let section1 = current_message.section_bytes(1)?;
+        let section1_length: i64 = current_message.read_key("section1Length")?;
+
+        assert_eq!(section1.len(), usize::try_from(section1_length)?);
+        assert!(current_message.section_bytes(42).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn section_range_bounds() {
+        assert_eq!(section_range(8, 20, 100), Some(8..28));
+        assert_eq!(section_range(80, 20, 100), Some(80..100));
+        assert_eq!(section_range(90, 20, 100), None);
+        assert_eq!(section_range(usize::MAX, 1, 100), None);
+        assert_eq!(section_range(1, usize::MAX, usize::MAX), None);
+    }
+
+    #[test]
+    fn read_fixed_size_array() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut current_message = handle.next()?.context("Message not some")?.try_clone()?;
+
+        current_message.write_values_and_geometry(&[1.0, 2.0], 2, 1)?;
+
+        let values: [f64; 2] = current_message.read_key_array("values")?;
+        let expected: Vec<f64> = current_message.read_key("values")?;
+        assert_eq!(values.to_vec(), expected);
+
+        let wrong_size = current_message.read_key_array::<3>("values");
+        assert!(matches!(wrong_size, Err(CodesError::WrongRequestedKeySize)));
+
+        let wrong_type = current_message.read_key_array::<1>("Ni");
+        assert!(matches!(
+            wrong_type,
+            Err(CodesError::WrongRequestedKeyType { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn data_hash() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let first = handle.next()?.context("Message not some")?.try_clone()?;
+        let first_clone = first.try_clone()?;
+        let second = handle.next()?.context("Message not some")?;
+
+        assert_eq!(first.data_hash()?, first_clone.data_hash()?);
+        assert_ne!(first.data_hash()?, second.data_hash()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn identity_key() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let first = handle.next()?.context("Message not some")?.try_clone()?;
+        let second = handle.next()?.context("Message not some")?;
+
+        let mut same_metadata = first.try_clone()?;
+        let values: Vec<f64> = same_metadata.read_key("values")?;
+        let shifted: Vec<f64> = values.iter().map(|value| value + 1.0).collect();
+        same_metadata.write_key("values", &shifted)?;
+
+        assert_ne!(first.data_hash()?, same_metadata.data_hash()?);
+        assert_eq!(first.identity_key()?, same_metadata.identity_key()?);
+        assert_ne!(first.identity_key()?, second.identity_key()?);
+
+        let identity = first.identity_key()?;
+        assert!(identity.starts_with(&format!("{}/", first.short_name()?)));
+        assert_eq!(identity.split('/').count(), 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_string_without_nul_termination() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let msg = handle.next()?.context("Message not some")?;
+
+        // ecCodes reports the length of this key without the nul byte
+        let version: String = msg.read_key("experimentVersionNumber")?;
+        assert_eq!(version, "0001");
+
+        let short_name: String = msg.read_key("shortName")?;
+        assert_eq!(short_name, "msl");
+
+        Ok(())
+    }
+
+    #[test]
+    fn force_numeric_read() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let msg = handle.next()?.context("Message not some")?;
+
+        // experimentVersionNumber is a string key containing a number
+        let version: String = msg.read_key("experimentVersionNumber")?;
+        let forced = msg.force_double("experimentVersionNumber")?;
+        assert!((forced - version.trim().parse::<f64>()?).abs() < 1e-9);
+        assert!(KeyRead::<f64>::read_key(msg, "experimentVersionNumber").is_err());
+
+        let latitude: f64 = msg.read_key("latitudeOfFirstGridPointInDegrees")?;
+        let forced = msg.force_long("latitudeOfFirstGridPointInDegrees")?;
+        assert!((f64::from(i32::try_from(forced)?) - latitude.trunc()).abs() < 1e-9);
+
+        Ok(())
+    }
+
+    #[test]
+    fn values_iter() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut msg = handle.next()?.context("Message not some")?.try_clone()?;
+
+        let values: Vec<f64> = msg.read_key("values")?;
+        let values_sum: f64 = values.iter().sum();
+        assert!((msg.values_iter(true)?.sum::<f64>() - values_sum).abs() < 1e-6);
+        assert_eq!(msg.values_iter(false)?.count(), values.len());
+
+        let missing_value = 9999.0;
+        let mut masked_values = values.clone();
+        masked_values[0] = missing_value;
+        masked_values[1] = missing_value;
+
+        msg.write_key("bitmapPresent", 1)?
+            .write_key("missingValue", missing_value)?
+            .write_key("values", &masked_values)?;
+
+        // sum of decoded values at gridpoints marked as present in the bitmap
+        let decoded: Vec<f64> = msg.read_key("values")?;
+        let bitmap: Vec<i64> = msg.read_key("bitmap")?;
+        let bitmap_sum: f64 = decoded
+            .iter()
+            .zip(&bitmap)
+            .filter(|(_, present)| **present == 1)
+            .map(|(value, _)| value)
+            .sum();
+
+        assert_eq!(bitmap.iter().filter(|present| **present == 0).count(), 2);
+        assert_eq!(msg.values_iter(true)?.count(), masked_values.len() - 2);
+        assert_eq!(msg.values_iter(false)?.count(), masked_values.len());
+
+        let iter_sum: f64 = msg.values_iter(true)?.sum();
+        assert!((iter_sum - bitmap_sum).abs() < 1e-6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn encoded_size() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        let (_, message_len) = unsafe { codes_get_message(current_message.message_handle)? };
+        let total_length: i64 = current_message.read_key("totalLength")?;
+
+        assert_eq!(current_message.encoded_size()?, message_len);
+        assert_eq!(
+            i64::try_from(current_message.encoded_size()?)?,
+            total_length
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn estimated_clone_cost() -> Result<()> {
+        let file_path = Path::new("./data/iceland-levels.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        let values: Vec<f64> = current_message.read_key("values")?;
+        let decoded = current_message.decoded_values_bytes()?;
+        let estimate = current_message.estimated_clone_cost()?;
+
+        assert_eq!(decoded, values.len() * 8);
+        assert_eq!(estimate, current_message.encoded_size()? + decoded);
+        assert!(estimate > decoded && estimate < 10 * decoded);
+
+        Ok(())
+    }
+
+    #[test]
+    fn csv_row() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        let row = current_message.to_csv_row(&["shortName", "level"], ',')?;
+        assert_eq!(row, "msl,0");
+
+        let row = current_message.to_csv_row(&["shortName", "doesNotExist", "level"], ';')?;
+        assert_eq!(row, "msl;;0");
+
+        Ok(())
+    }
+
     #[test]
     fn missing_key() -> Result<()> {
         let file_path = Path::new("./data/iceland.grib");
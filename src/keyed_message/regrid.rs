@@ -0,0 +1,369 @@
+use crate::{errors::CodesError, GridDefinition, GridType, KeyRead, KeyWrite, KeyedMessage};
+
+/// Tolerance for gridpoint positions, in units of grid increment
+const POSITION_EPSILON: f64 = 1e-6;
+
+/// Method of computing values on the target grid in [`KeyedMessage::regrid()`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum Interpolation {
+    /// Value of the closest source gridpoint
+    Nearest,
+    /// Weighted mean of four surrounding source gridpoints
+    #[default]
+    Bilinear,
+}
+
+impl KeyedMessage {
+    /// Resamples the message onto the target regular latitude-longitude grid
+    /// and returns the new message.
+    ///
+    /// Values are interpolated from the source grid with chosen [`Interpolation`].
+    /// Target gridpoints outside of the source grid, or depending on missing source values,
+    /// are encoded as missing with a bitmap. Longitudes wrap around when the source grid is global.
+    ///
+    /// ecCodes can only change the grid definition (`codes_grib_util_set_spec` expects
+    /// values already on the target grid), so interpolation is done by this crate
+    /// and only regular latitude-longitude source grids are supported.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{CodesHandle, Interpolation, KeyRead, ProductKind};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let mut target = message.grid_definition()?;
+    ///  target.ni = 25;
+    ///  target.nj = 9;
+    ///  target.i_increment *= 2.0;
+    ///  target.j_increment *= 2.0;
+    ///
+    ///  let coarse = message.regrid(&target, Interpolation::Bilinear)?;
+    ///  let ni: i64 = coarse.read_key("Ni")?;
+    ///  assert_eq!(ni, 25);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::UnsupportedSourceGrid`] when the message is not on a regular latitude-longitude grid.
+    ///
+    /// Returns [`CodesError::UnexpectedKeyValue`] when the number of points of the source grid is not positive
+    /// or the number of points of the target grid is negative or overflows.
+    ///
+    /// Returns [`CodesError::WrongRequestedKeySize`] when the number of source values
+    /// is not equal to the number of source gridpoints.
+    ///
+    /// Returns [`CodesError`] when the message cannot be cloned or when grid keys
+    /// or values cannot be read or written.
+    pub fn regrid(
+        &self,
+        target: &GridDefinition,
+        interpolation: Interpolation,
+    ) -> Result<KeyedMessage, CodesError> {
+        let grid_type = self.grid_type()?;
+        if grid_type != GridType::RegularLatLon {
+            return Err(CodesError::UnsupportedSourceGrid(grid_type));
+        }
+
+        let source = SourceGrid::new(self)?;
+
+        let ni = usize::try_from(target.ni)
+            .map_err(|_| CodesError::UnexpectedKeyValue("Ni".to_owned()))?;
+        let nj = usize::try_from(target.nj)
+            .map_err(|_| CodesError::UnexpectedKeyValue("Nj".to_owned()))?;
+        let target_len = ni
+            .checked_mul(nj)
+            .ok_or_else(|| CodesError::UnexpectedKeyValue("Nj".to_owned()))?;
+
+        let mut values = Vec::with_capacity(target_len);
+
+        for index in 0..target_len {
+            let (i, j) = if target.j_points_are_consecutive {
+                (index / nj, index % nj)
+            } else {
+                (index % ni, index / ni)
+            };
+            let (lat, lon) = grid_coordinate(target, i, j);
+
+            values.push(source.interpolate(lat, lon, interpolation));
+        }
+
+        let mut new_message = self.try_clone()?;
+        new_message.set_grid_definition(target)?;
+
+        if values.iter().any(Option::is_none) {
+            new_message.set_values_masked(&values)?;
+        } else {
+            let values: Vec<f64> = values.into_iter().flatten().collect();
+            new_message.write_key("values", values.as_slice())?;
+        }
+
+        Ok(new_message)
+    }
+}
+
+/// Values of the message being regridded together with its geometry
+struct SourceGrid {
+    grid: GridDefinition,
+    values: Vec<f64>,
+    /// Value of missing gridpoints, only when the message has a bitmap
+    missing_value: Option<f64>,
+    ni: usize,
+    nj: usize,
+    is_global: bool,
+}
+
+impl SourceGrid {
+    #[allow(clippy::cast_precision_loss)] // grid dimensions are far below f64 mantissa range
+    fn new(message: &KeyedMessage) -> Result<Self, CodesError> {
+        let grid = message.grid_definition()?;
+        let values: Vec<f64> = message.read_key("values")?;
+
+        let ni = usize::try_from(grid.ni)
+            .ok()
+            .filter(|ni| *ni > 0)
+            .ok_or_else(|| CodesError::UnexpectedKeyValue("Ni".to_owned()))?;
+        let nj = usize::try_from(grid.nj)
+            .ok()
+            .filter(|nj| *nj > 0)
+            .ok_or_else(|| CodesError::UnexpectedKeyValue("Nj".to_owned()))?;
+
+        if ni.checked_mul(nj) != Some(values.len()) {
+            return Err(CodesError::WrongRequestedKeySize);
+        }
+
+        let missing_value = if KeyRead::<bool>::read_key(message, "bitmapPresent")? {
+            Some(message.read_key("missingValue")?)
+        } else {
+            None
+        };
+
+        let is_global = (ni as f64 * grid.i_increment - 360.0).abs() < POSITION_EPSILON;
+
+        Ok(Self {
+            grid,
+            values,
+            missing_value,
+            ni,
+            nj,
+            is_global,
+        })
+    }
+
+    fn interpolate(&self, lat: f64, lon: f64, interpolation: Interpolation) -> Option<f64> {
+        let (i_position, j_position) = self.fractional_position(lat, lon)?;
+
+        match interpolation {
+            Interpolation::Nearest => self.value_at(i_position.round(), j_position.round()),
+            Interpolation::Bilinear => {
+                let (i_floor, j_floor) = (i_position.floor(), j_position.floor());
+                let (i_weight, j_weight) = (i_position - i_floor, j_position - j_floor);
+
+                let mut sum = 0.0;
+
+                for (i_shift, j_shift, weight) in [
+                    (0.0, 0.0, (1.0 - i_weight) * (1.0 - j_weight)),
+                    (1.0, 0.0, i_weight * (1.0 - j_weight)),
+                    (0.0, 1.0, (1.0 - i_weight) * j_weight),
+                    (1.0, 1.0, i_weight * j_weight),
+                ] {
+                    // points with zero weight can be outside of the grid on its last row or column
+                    if weight < POSITION_EPSILON {
+                        continue;
+                    }
+
+                    sum += weight * self.value_at(i_floor + i_shift, j_floor + j_shift)?;
+                }
+
+                Some(sum)
+            }
+        }
+    }
+
+    /// Returns the position of the coordinate in units of source gridpoints,
+    /// or `None` when it is outside of the source grid.
+    #[allow(clippy::cast_precision_loss)] // grid dimensions are far below f64 mantissa range
+    fn fractional_position(&self, lat: f64, lon: f64) -> Option<(f64, f64)> {
+        let grid = &self.grid;
+        let (ni, nj) = (self.ni, self.nj);
+
+        let lon_offset = if grid.i_scans_negatively {
+            grid.first_longitude - lon
+        } else {
+            lon - grid.first_longitude
+        };
+        let lat_offset = if grid.j_scans_positively {
+            lat - grid.first_latitude
+        } else {
+            grid.first_latitude - lat
+        };
+
+        let mut i_position = lon_offset.rem_euclid(360.0) / grid.i_increment;
+        let j_position = lat_offset / grid.j_increment;
+
+        // offsets just below a full circle belong to the first column
+        if (i_position * grid.i_increment - 360.0).abs() < POSITION_EPSILON * grid.i_increment {
+            i_position = 0.0;
+        }
+
+        let max_i = if self.is_global {
+            ni as f64
+        } else {
+            (ni - 1) as f64
+        };
+        let max_j = (nj - 1) as f64;
+
+        if i_position > max_i + POSITION_EPSILON
+            || j_position < -POSITION_EPSILON
+            || j_position > max_j + POSITION_EPSILON
+        {
+            return None;
+        }
+
+        Some((i_position.clamp(0.0, max_i), j_position.clamp(0.0, max_j)))
+    }
+
+    /// Returns the value at gridpoint `(i, j)`, wrapping `i` around on global grids.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)] // positions are clamped to the grid
+    fn value_at(&self, i: f64, j: f64) -> Option<f64> {
+        let (ni, nj) = (self.ni, self.nj);
+        let (mut i, j) = (i as usize, j as usize);
+
+        if i >= ni && self.is_global {
+            i -= ni;
+        }
+
+        if i >= ni || j >= nj {
+            return None;
+        }
+
+        let index = if self.grid.j_points_are_consecutive {
+            i * nj + j
+        } else {
+            j * ni + i
+        };
+
+        // length of values is checked against the grid dimensions in `new()`
+        let value = self.values[index];
+
+        // missing values are set by ecCodes to exactly `missingValue`
+        let is_missing = self.missing_value == Some(value);
+
+        (!is_missing).then_some(value)
+    }
+}
+
+/// Returns `(latitude, longitude)` of the gridpoint `(i, j)` of a regular grid.
+#[allow(clippy::cast_precision_loss)] // grid dimensions are far below f64 mantissa range
+pub(super) fn grid_coordinate(grid: &GridDefinition, i: usize, j: usize) -> (f64, f64) {
+    let i_step = if grid.i_scans_negatively {
+        -grid.i_increment
+    } else {
+        grid.i_increment
+    };
+    let j_step = if grid.j_scans_positively {
+        grid.j_increment
+    } else {
+        -grid.j_increment
+    };
+
+    (
+        grid.first_latitude + j as f64 * j_step,
+        grid.first_longitude + i as f64 * i_step,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{Context, Result};
+
+    use crate::codes_handle::{CodesHandle, ProductKind};
+    use crate::{FallibleStreamingIterator, Interpolation, KeyRead, KeyWrite};
+    use std::path::Path;
+
+    #[test]
+    fn regrid_to_coarser_grid() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let msg = handle.next()?.context("Message not some")?;
+
+        let source = msg.grid_definition()?;
+        let source_values: Vec<f64> = msg.read_key("values")?;
+
+        let mut target = source;
+        target.ni = (source.ni + 1) / 2;
+        target.nj = (source.nj + 1) / 2;
+        target.i_increment *= 2.0;
+        target.j_increment *= 2.0;
+
+        for interpolation in [Interpolation::Nearest, Interpolation::Bilinear] {
+            let regridded = msg.regrid(&target, interpolation)?;
+
+            let ni: i64 = regridded.read_key("Ni")?;
+            let nj: i64 = regridded.read_key("Nj")?;
+            assert_eq!((ni, nj), (target.ni, target.nj));
+
+            let values: Vec<f64> = regridded.read_key("values")?;
+            assert_eq!(values.len(), usize::try_from(ni * nj)?);
+
+            // every target gridpoint coincides with a source gridpoint
+            let source_ni = usize::try_from(source.ni)?;
+            let target_ni = usize::try_from(target.ni)?;
+            for (index, value) in values.iter().enumerate() {
+                let (i, j) = (index % target_ni, index / target_ni);
+                let expected = source_values[2 * j * source_ni + 2 * i];
+
+                assert!((value - expected).abs() < 1e-2 * expected.abs().max(1.0));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn regrid_outside_source_is_missing() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let msg = handle.next()?.context("Message not some")?;
+
+        let mut target = msg.grid_definition()?;
+        target.first_latitude += 1.0;
+        target.last_latitude += 1.0;
+
+        let regridded = msg.regrid(&target, Interpolation::Nearest)?;
+
+        let missing: i64 = regridded.read_key("numberOfMissing")?;
+        assert_eq!(missing, 4 * target.ni);
+
+        Ok(())
+    }
+
+    #[test]
+    fn regrid_without_bitmap_keeps_missing_value() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.context("Message not some")?.try_clone()?;
+
+        // without bitmap, a value equal to `missingValue` is an ordinary value
+        let missing_value: f64 = msg.read_key("missingValue")?;
+        let mut values: Vec<f64> = msg.read_key("values")?;
+        values[0] = missing_value;
+        msg.write_key("bitmapPresent", 0)?
+            .write_key("values", values.as_slice())?;
+
+        let target = msg.grid_definition()?;
+        let regridded = msg.regrid(&target, Interpolation::Nearest)?;
+
+        let missing: i64 = regridded.read_key("numberOfMissing")?;
+        assert_eq!(missing, 0);
+
+        Ok(())
+    }
+}
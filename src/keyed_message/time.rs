@@ -0,0 +1,349 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+
+use crate::{errors::CodesError, DynamicKeyType, KeyRead, KeyWrite, KeyedMessage};
+
+impl KeyedMessage {
+    /// Sets the reference date and time of the message by writing `dataDate` (as `YYYYMMDD`)
+    /// and `dataTime` (as `HHMM`) keys.
+    ///
+    /// Both keys are integers, so eg. 06:00 is written as `600`, which ecCodes encodes correctly.
+    /// Seconds and fractions of seconds are discarded, as GRIB reference time has a precision of minutes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle, KeyRead};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  use chrono::NaiveDate;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  let datetime = NaiveDate::from_ymd_opt(2024, 3, 1)
+    ///      .context("invalid date")?
+    ///      .and_hms_opt(6, 0, 0)
+    ///      .context("invalid time")?;
+    ///  message.set_data_datetime(datetime)?;
+    ///
+    ///  let data_time: i64 = message.read_key("dataTime")?;
+    ///  assert_eq!(data_time, 600);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal) if ecCodes fails to write the keys.
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+    pub fn set_data_datetime(&mut self, datetime: NaiveDateTime) -> Result<(), CodesError> {
+        let date = i64::from(datetime.year()) * 10000
+            + i64::from(datetime.month()) * 100
+            + i64::from(datetime.day());
+        let time = i64::from(datetime.hour()) * 100 + i64::from(datetime.minute());
+
+        self.write_key("dataDate", date)?;
+        self.write_key("dataTime", time)?;
+
+        Ok(())
+    }
+
+    /// Sets the forecast step of the message by writing `stepUnits` and `step` keys.
+    ///
+    /// The step is written in hours when it is a whole number of hours, otherwise in minutes.
+    /// Seconds are discarded, as GRIB steps are rarely encoded with a precision higher than minutes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle, KeyRead};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  use chrono::Duration;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  message.set_step(Duration::hours(6))?;
+    ///
+    ///  let step: i64 = message.read_key("step")?;
+    ///  assert_eq!(step, 6);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::UnexpectedKeyValue`] when the step is negative.
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal) if ecCodes fails to write the keys.
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+    pub fn set_step(&mut self, step: Duration) -> Result<(), CodesError> {
+        if step < Duration::zero() {
+            return Err(CodesError::UnexpectedKeyValue("step".to_owned()));
+        }
+
+        let minutes = step.num_minutes();
+
+        if minutes % 60 == 0 {
+            self.write_key("stepUnits", "h")?
+                .write_key("step", minutes / 60)?;
+        } else {
+            self.write_key("stepUnits", "m")?
+                .write_key("step", minutes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the forecast step of the message as [`Duration`], from `endStep` and `stepUnits` keys.
+    ///
+    /// For messages describing a time range (eg. accumulations) the end of the range is returned,
+    /// so the step can be added to the reference time to get the validity time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  use chrono::Duration;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  message.set_step(Duration::minutes(90))?;
+    ///  assert_eq!(message.forecast_step()?, Duration::minutes(90));
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::UnexpectedKeyValue`] when `stepUnits` is not a unit of time
+    /// supported by this function.
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal) if ecCodes fails to read the keys.
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+    pub fn forecast_step(&self) -> Result<Duration, CodesError> {
+        let step: i64 = self.read_key("endStep")?;
+        let step_units: String = self.read_key("stepUnits")?;
+
+        let unit = match step_units.as_str() {
+            "s" => Duration::seconds(1),
+            "m" => Duration::minutes(1),
+            "15m" => Duration::minutes(15),
+            "30m" => Duration::minutes(30),
+            "h" => Duration::hours(1),
+            "3h" => Duration::hours(3),
+            "6h" => Duration::hours(6),
+            "12h" => Duration::hours(12),
+            "D" => Duration::days(1),
+            _ => return Err(CodesError::UnexpectedKeyValue("stepUnits".to_owned())),
+        };
+
+        unit.checked_mul(
+            i32::try_from(step)
+                .map_err(|_| CodesError::UnexpectedKeyValue("endStep".to_owned()))?,
+        )
+        .ok_or_else(|| CodesError::UnexpectedKeyValue("endStep".to_owned()))
+    }
+
+    /// Returns the validity time of the message.
+    ///
+    /// When `validityDate` and `validityTime` keys are present they are used directly.
+    /// Otherwise the validity time is computed as the reference time (`dataDate` and `dataTime`)
+    /// plus [`forecast_step()`](KeyedMessage::forecast_step), so both families of files are handled uniformly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  use chrono::{Duration, NaiveDate};
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  let base = NaiveDate::from_ymd_opt(2024, 3, 1)
+    ///      .context("invalid date")?
+    ///      .and_hms_opt(18, 0, 0)
+    ///      .context("invalid time")?;
+    ///  message.set_data_datetime(base)?;
+    ///  message.set_step(Duration::hours(12))?;
+    ///
+    ///  assert_eq!(message.computed_validity()?, base + Duration::hours(12));
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::UnexpectedKeyValue`] when date or time keys do not describe a valid date and time.
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal) if ecCodes fails to read the keys.
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+    pub fn computed_validity(&self) -> Result<NaiveDateTime, CodesError> {
+        if let (Some(DynamicKeyType::Int(date)), Some(DynamicKeyType::Int(time))) = (
+            self.read_key_dynamic_if_present("validityDate")?,
+            self.read_key_dynamic_if_present("validityTime")?,
+        ) {
+            return datetime_from_keys(date, time, "validityDate", "validityTime");
+        }
+
+        self.base_plus_step()
+    }
+
+    /// Computes the validity time from the reference time and the forecast step.
+    fn base_plus_step(&self) -> Result<NaiveDateTime, CodesError> {
+        let date: i64 = self.read_key("dataDate")?;
+        let time: i64 = self.read_key("dataTime")?;
+
+        let base = datetime_from_keys(date, time, "dataDate", "dataTime")?;
+
+        base.checked_add_signed(self.forecast_step()?)
+            .ok_or_else(|| CodesError::UnexpectedKeyValue("endStep".to_owned()))
+    }
+}
+
+/// Parses date as `YYYYMMDD` and time as `HHMM`, reporting the name of the invalid key.
+fn datetime_from_keys(
+    date: i64,
+    time: i64,
+    date_key: &str,
+    time_key: &str,
+) -> Result<NaiveDateTime, CodesError> {
+    let invalid_date = || CodesError::UnexpectedKeyValue(date_key.to_owned());
+    let invalid_time = || CodesError::UnexpectedKeyValue(time_key.to_owned());
+
+    let date = NaiveDate::from_ymd_opt(
+        i32::try_from(date / 10000).map_err(|_| invalid_date())?,
+        u32::try_from(date / 100 % 100).map_err(|_| invalid_date())?,
+        u32::try_from(date % 100).map_err(|_| invalid_date())?,
+    )
+    .ok_or_else(invalid_date)?;
+
+    date.and_hms_opt(
+        u32::try_from(time / 100).map_err(|_| invalid_time())?,
+        u32::try_from(time % 100).map_err(|_| invalid_time())?,
+        0,
+    )
+    .ok_or_else(invalid_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{Context, Result};
+    use chrono::{Duration, NaiveDate};
+
+    use crate::codes_handle::{CodesHandle, ProductKind};
+    use crate::{FallibleStreamingIterator, KeyRead, KeyWrite};
+    use std::{fs::remove_file, path::Path};
+
+    #[test]
+    fn data_datetime_roundtrip() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let out_path = Path::new("./data/iceland_datetime.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut message = handle.next()?.context("Message not some")?.try_clone()?;
+
+        let datetime = NaiveDate::from_ymd_opt(2023, 7, 9)
+            .context("invalid date")?
+            .and_hms_opt(6, 5, 0)
+            .context("invalid time")?;
+
+        message.set_data_datetime(datetime)?;
+        message.write_to_file(out_path, false)?;
+
+        let mut handle = CodesHandle::new_from_file(out_path, product_kind)?;
+        let message = handle.next()?.context("Message not some")?;
+
+        let data_date: i64 = message.read_key("dataDate")?;
+        let data_time: i64 = message.read_key("dataTime")?;
+
+        let parsed = NaiveDate::from_ymd_opt(
+            i32::try_from(data_date / 10000)?,
+            u32::try_from(data_date / 100 % 100)?,
+            u32::try_from(data_date % 100)?,
+        )
+        .context("invalid date")?
+        .and_hms_opt(
+            u32::try_from(data_time / 100)?,
+            u32::try_from(data_time % 100)?,
+            0,
+        )
+        .context("invalid time")?;
+
+        assert_eq!(data_time, 605);
+        assert_eq!(parsed, datetime);
+
+        remove_file(out_path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn step_roundtrip() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let out_path = Path::new("./data/iceland_step.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut message = handle.next()?.context("Message not some")?.try_clone()?;
+
+        message.set_step(Duration::hours(6))?;
+        message.write_to_file(out_path, false)?;
+
+        let mut handle = CodesHandle::new_from_file(out_path, product_kind)?;
+        let mut message = handle.next()?.context("Message not some")?.try_clone()?;
+        remove_file(out_path)?;
+
+        message.write_key("stepUnits", "h")?;
+        let step: i64 = message.read_key("step")?;
+        assert_eq!(step, 6);
+
+        message.set_step(Duration::minutes(90))?;
+        let step: i64 = message.read_key("step")?;
+        assert_eq!(step, 90);
+
+        assert!(message.set_step(Duration::hours(-1)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn computed_validity_from_base_and_step() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut message = handle.next()?.context("Message not some")?.try_clone()?;
+
+        let base = NaiveDate::from_ymd_opt(2023, 12, 31)
+            .context("invalid date")?
+            .and_hms_opt(18, 0, 0)
+            .context("invalid time")?;
+
+        message.set_data_datetime(base)?;
+        message.set_step(Duration::minutes(390))?;
+
+        let expected = base + Duration::minutes(390);
+
+        assert_eq!(message.forecast_step()?, Duration::minutes(390));
+        assert_eq!(message.base_plus_step()?, expected);
+        assert_eq!(message.computed_validity()?, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{errors::CodesError, KeyRead, KeyedMessage};
+
+/// Supported conversions as `(from, to, scale, offset)`, where `converted = value * scale + offset`
+const CONVERSIONS: [(&str, &str, f64, f64); 6] = [
+    ("K", "C", 1.0, -273.15),
+    ("C", "K", 1.0, 273.15),
+    ("Pa", "hPa", 0.01, 0.0),
+    ("hPa", "Pa", 100.0, 0.0),
+    ("m", "mm", 1000.0, 0.0),
+    ("mm", "m", 0.001, 0.0),
+];
+
+/// Maps different spellings of units to the names used in [`CONVERSIONS`].
+fn canonical_units(units: &str) -> &str {
+    match units.trim() {
+        "°C" | "degC" | "deg C" => "C",
+        units => units,
+    }
+}
+
+impl KeyedMessage {
+    /// Reads the `units` key, describing units of data values of the message (eg. `"K"` or `"Pa"`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError`] when the key cannot be read.
+    pub fn units(&self) -> Result<String, CodesError> {
+        self.read_key("units")
+    }
+
+    /// Returns data values of the message converted from [`units()`](KeyedMessage::units) to `target_units`.
+    ///
+    /// Supported are common meteorological conversions between `K` and `C` (also spelled `°C` or `degC`),
+    /// `Pa` and `hPa`, and `m` and `mm` (eg. for precipitation). When the units are the same
+    /// values are returned unchanged. Missing values (equal to `missingValue` when the message
+    /// contains a bitmap) are not converted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  assert_eq!(message.units()?, "Pa");
+    ///  let pressure_hpa = message.values_converted("hPa")?;
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::UnsupportedUnitConversion`] when conversion between the units is not supported.
+    ///
+    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// when `units`, `values`, `bitmapPresent` or `missingValue` keys cannot be read.
+    #[allow(clippy::float_cmp)] // missing values are set by ecCodes to exactly `missingValue`
+    pub fn values_converted(&self, target_units: &str) -> Result<Vec<f64>, CodesError> {
+        let units = self.units()?;
+        let (from, to) = (canonical_units(&units), canonical_units(target_units));
+
+        let (scale, offset) = if from == to {
+            (1.0, 0.0)
+        } else {
+            CONVERSIONS
+                .iter()
+                .find(|conversion| conversion.0 == from && conversion.1 == to)
+                .map(|conversion| (conversion.2, conversion.3))
+                .ok_or_else(|| CodesError::UnsupportedUnitConversion {
+                    from: units.clone(),
+                    to: target_units.to_owned(),
+                })?
+        };
+
+        let missing_value: Option<f64> = if KeyRead::<bool>::read_key(self, "bitmapPresent")? {
+            Some(self.read_key_unchecked("missingValue")?)
+        } else {
+            None
+        };
+
+        let values: Vec<f64> = self.read_key("values")?;
+
+        Ok(values
+            .into_iter()
+            .map(|value| {
+                if missing_value == Some(value) {
+                    value
+                } else {
+                    value.mul_add(scale, offset)
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{Context, Result};
+
+    use crate::codes_handle::{CodesHandle, ProductKind};
+    use crate::{CodesError, FallibleStreamingIterator, KeyRead};
+    use std::path::Path;
+
+    #[test]
+    fn convert_pressure_to_hpa() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let msg = handle.next()?.context("Message not some")?;
+
+        assert_eq!(msg.units()?, "Pa");
+
+        let values: Vec<f64> = msg.read_key("values")?;
+        let converted = msg.values_converted("hPa")?;
+
+        assert_eq!(values.len(), converted.len());
+        assert!(values
+            .iter()
+            .zip(&converted)
+            .all(|(pa, hpa)| (pa / hpa - 100.0).abs() < 1e-9));
+
+        assert_eq!(msg.values_converted("Pa")?, values);
+
+        let result = msg.values_converted("K");
+        assert!(matches!(
+            result,
+            Err(CodesError::UnsupportedUnitConversion { .. })
+        ));
+
+        Ok(())
+    }
+}
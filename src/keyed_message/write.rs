@@ -1,77 +1,172 @@
 use std::{fs::OpenOptions, io::Write, path::Path, slice};
 
+use log::debug;
+
 use crate::{
-    errors::CodesError,
+    errors::{CodesError, CodesInternal},
     intermediate_bindings::{
-        codes_get_message, codes_set_bytes, codes_set_double, codes_set_double_array,
-        codes_set_long, codes_set_long_array, codes_set_string,
+        codes_copy_key, codes_get_message, codes_set_bytes, codes_set_double,
+        codes_set_double_array, codes_set_long, codes_set_long_array, codes_set_string,
     },
-    KeyedMessage,
+    FallibleIterator, KeyRead, KeyedMessage, KeysIteratorFlags,
 };
 
 use super::KeyWrite;
 
 impl KeyWrite<i64> for KeyedMessage {
-    fn write_key(&mut self, name: &str, value: i64) -> Result<(), CodesError> {
-        unsafe { codes_set_long(self.message_handle, name, value) }
+    fn write_key(&mut self, name: &str, value: i64) -> Result<&mut Self, CodesError> {
+        unsafe {
+            codes_set_long(self.message_handle, name, value)?;
+        }
+
+        Ok(self)
     }
 }
 
 impl KeyWrite<f64> for KeyedMessage {
-    fn write_key(&mut self, name: &str, value: f64) -> Result<(), CodesError> {
-        unsafe { codes_set_double(self.message_handle, name, value) }
+    fn write_key(&mut self, name: &str, value: f64) -> Result<&mut Self, CodesError> {
+        unsafe {
+            codes_set_double(self.message_handle, name, value)?;
+        }
+
+        Ok(self)
     }
 }
 
 impl KeyWrite<&[i64]> for KeyedMessage {
-    fn write_key(&mut self, name: &str, value: &[i64]) -> Result<(), CodesError> {
-        unsafe { codes_set_long_array(self.message_handle, name, value) }
+    fn write_key(&mut self, name: &str, value: &[i64]) -> Result<&mut Self, CodesError> {
+        unsafe {
+            codes_set_long_array(self.message_handle, name, value)?;
+        }
+
+        Ok(self)
     }
 }
 
 impl KeyWrite<&[f64]> for KeyedMessage {
-    fn write_key(&mut self, name: &str, value: &[f64]) -> Result<(), CodesError> {
-        unsafe { codes_set_double_array(self.message_handle, name, value) }
+    fn write_key(&mut self, name: &str, value: &[f64]) -> Result<&mut Self, CodesError> {
+        unsafe {
+            codes_set_double_array(self.message_handle, name, value)?;
+        }
+
+        Ok(self)
     }
 }
 
 impl KeyWrite<&[u8]> for KeyedMessage {
-    fn write_key(&mut self, name: &str, value: &[u8]) -> Result<(), CodesError> {
-        unsafe { codes_set_bytes(self.message_handle, name, value) }
+    fn write_key(&mut self, name: &str, value: &[u8]) -> Result<&mut Self, CodesError> {
+        unsafe {
+            codes_set_bytes(self.message_handle, name, value)?;
+        }
+
+        Ok(self)
     }
 }
 
 impl KeyWrite<&Vec<i64>> for KeyedMessage {
-    fn write_key(&mut self, name: &str, value: &Vec<i64>) -> Result<(), CodesError> {
-        unsafe { codes_set_long_array(self.message_handle, name, value) }
+    fn write_key(&mut self, name: &str, value: &Vec<i64>) -> Result<&mut Self, CodesError> {
+        unsafe {
+            codes_set_long_array(self.message_handle, name, value)?;
+        }
+
+        Ok(self)
     }
 }
 
 impl KeyWrite<&Vec<f64>> for KeyedMessage {
-    fn write_key(&mut self, name: &str, value: &Vec<f64>) -> Result<(), CodesError> {
-        unsafe { codes_set_double_array(self.message_handle, name, value) }
+    fn write_key(&mut self, name: &str, value: &Vec<f64>) -> Result<&mut Self, CodesError> {
+        unsafe {
+            codes_set_double_array(self.message_handle, name, value)?;
+        }
+
+        Ok(self)
     }
 }
 
 impl KeyWrite<&Vec<u8>> for KeyedMessage {
-    fn write_key(&mut self, name: &str, value: &Vec<u8>) -> Result<(), CodesError> {
-        unsafe { codes_set_bytes(self.message_handle, name, value) }
+    fn write_key(&mut self, name: &str, value: &Vec<u8>) -> Result<&mut Self, CodesError> {
+        unsafe {
+            codes_set_bytes(self.message_handle, name, value)?;
+        }
+
+        Ok(self)
     }
 }
 
 impl KeyWrite<&str> for KeyedMessage {
-    fn write_key(&mut self, name: &str, value: &str) -> Result<(), CodesError> {
-        unsafe { codes_set_string(self.message_handle, name, value) }
+    fn write_key(&mut self, name: &str, value: &str) -> Result<&mut Self, CodesError> {
+        unsafe {
+            codes_set_string(self.message_handle, name, value)?;
+        }
+
+        Ok(self)
     }
 }
 
 impl KeyWrite<&String> for KeyedMessage {
-    fn write_key(&mut self, name: &str, value: &String) -> Result<(), CodesError> {
-        unsafe { codes_set_string(self.message_handle, name, value) }
+    fn write_key(&mut self, name: &str, value: &String) -> Result<&mut Self, CodesError> {
+        unsafe {
+            codes_set_string(self.message_handle, name, value)?;
+        }
+
+        Ok(self)
     }
 }
 
 impl KeyedMessage {
+    /// Same as [`write_key()`](KeyWrite::write_key) but reads the key back after writing
+    /// and checks that it holds the written value.
+    ///
+    /// Reading back with [`read_key()`](KeyRead::read_key) also checks the native type of the key,
+    /// so this gives a clear error when eg. a float is written to an integer key,
+    /// which ecCodes would silently convert. Note that the key is written before the check,
+    /// so the message is modified even when an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  # use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  # use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  # let file_path = Path::new("./data/iceland.grib");
+    ///  # let product_kind = ProductKind::GRIB;
+    ///  #
+    ///  let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  message.write_key_checked("level", 850)?;
+    ///  assert!(message.write_key_checked("level", 850.0).is_err());
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::WrongRequestedKeyType`] when the native type of the key
+    /// does not match the written type.
+    ///
+    /// Returns [`CodesError::UnexpectedKeyValue`] when the value read back differs from the written one.
+    ///
+    /// This function will return [`CodesInternal`] if ecCodes fails to write or read the key.
+    pub fn write_key_checked<T>(&mut self, name: &str, value: T) -> Result<&mut Self, CodesError>
+    where
+        Self: KeyWrite<T> + KeyRead<T>,
+        T: PartialEq + Clone,
+    {
+        let expected = value.clone();
+        KeyWrite::<T>::write_key(self, name, value)?;
+        let written: T = self.read_key(name)?;
+
+        if written != expected {
+            return Err(CodesError::UnexpectedKeyValue(name.to_owned()));
+        }
+
+        Ok(self)
+    }
+
     /// Function to write given `KeyedMessage` to a file at provided path.
     /// If file does not exists it will be created.
     /// If `append` is set to `true` file will be opened in append mode
@@ -108,7 +203,7 @@ impl KeyedMessage {
     /// Returns [`CodesError::FileHandlingInterrupted`] when the file cannot be opened,
     /// created or correctly written.
     ///
-    /// Returns [`CodesInternal`](crate::errors::CodesInternal)
+    /// Returns [`CodesInternal`]
     /// when internal ecCodes function returns non-zero code.
     pub fn write_to_file<P: AsRef<Path>>(
         &self,
@@ -127,6 +222,470 @@ impl KeyedMessage {
 
         Ok(())
     }
+
+    /// Writes `Ni`, `Nj` and `values` keys in the order required by ecCodes,
+    /// so that the grid geometry stays consistent with the number of values.
+    ///
+    /// The length of `values` is checked against `ni * nj` before anything is written.
+    ///
+    /// Other geometry keys (eg. coordinates of the last gridpoint or increments)
+    /// are not updated and should be written separately when needed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{CodesHandle, KeyRead, ProductKind};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  message.write_values_and_geometry(&[0.0; 6], 3, 2)?;
+    ///
+    ///  let number_of_values: i64 = message.read_key("numberOfValues")?;
+    ///  assert_eq!(number_of_values, 6);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::WrongRequestedKeySize`] when length of `values` is not equal to `ni * nj`
+    /// and [`CodesError::UnexpectedKeyValue`] when `ni` or `nj` is negative or their product overflows.
+    ///
+    /// Returns [`CodesInternal`]
+    /// when internal ecCodes function returns non-zero code.
+    pub fn write_values_and_geometry(
+        &mut self,
+        values: &[f64],
+        ni: i64,
+        nj: i64,
+    ) -> Result<(), CodesError> {
+        let columns =
+            usize::try_from(ni).map_err(|_| CodesError::UnexpectedKeyValue("Ni".to_owned()))?;
+        let rows =
+            usize::try_from(nj).map_err(|_| CodesError::UnexpectedKeyValue("Nj".to_owned()))?;
+        let expected_len = columns
+            .checked_mul(rows)
+            .ok_or_else(|| CodesError::UnexpectedKeyValue("Nj".to_owned()))?;
+
+        if values.len() != expected_len {
+            return Err(CodesError::WrongRequestedKeySize);
+        }
+
+        self.write_key("Ni", ni)?;
+        self.write_key("Nj", nj)?;
+        self.write_key("values", values)?;
+
+        Ok(())
+    }
+
+    /// Writes an array key only when `values` have the same length as the current value of the key,
+    /// guarding against accidental resizing of arrays that other keys depend on (eg. `values` and the grid).
+    ///
+    /// Use [`write_key()`](KeyWrite::write_key) when the size of the array should change.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{CodesHandle, KeyRead, ProductKind};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  let values: Vec<f64> = message.read_key("values")?;
+    ///  let doubled: Vec<f64> = values.iter().map(|v| v * 2.0).collect();
+    ///
+    ///  message.replace_array("values", &doubled)?;
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::WrongRequestedKeySize`] when the length of `values`
+    /// differs from the current size of the key. Nothing is written in that case.
+    ///
+    /// Returns [`CodesInternal`]
+    /// when internal ecCodes function returns non-zero code.
+    pub fn replace_array<T>(&mut self, name: &str, values: &[T]) -> Result<(), CodesError>
+    where
+        Self: for<'a> KeyWrite<&'a [T]>,
+    {
+        if self.get_key_size(name)? != values.len() {
+            return Err(CodesError::WrongRequestedKeySize);
+        }
+
+        self.write_key(name, values)?;
+
+        Ok(())
+    }
+
+    /// Copies values of all writable keys from `source` onto this message,
+    /// eg. to stamp a sample message with metadata of a real message.
+    ///
+    /// Keys are copied in the order of their definition, so that keys describing
+    /// the layout of the message are set before dependent keys. Keys that are read-only
+    /// or not defined in this message are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{CodesHandle, KeyRead, ProductKind};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland-surface.grib"), ProductKind::GRIB)?;
+    ///  let first = handle.next()?.context("no message")?.try_clone()?;
+    ///  let second = handle.next()?.context("no message")?;
+    ///
+    ///  let mut copy = first.try_clone()?;
+    ///  copy.copy_from(second)?;
+    ///
+    ///  let short_name: String = copy.read_key("shortName")?;
+    ///  assert_eq!(short_name, second.short_name()?);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`] when keys of `source` cannot be iterated
+    /// or when any key cannot be copied for other reason than being read-only or not defined.
+    pub fn copy_from(&mut self, source: &KeyedMessage) -> Result<(), CodesError> {
+        let key_names: Vec<String> = source
+            .new_keys_iterator(
+                &[
+                    KeysIteratorFlags::SkipReadOnly,
+                    KeysIteratorFlags::SkipFunction,
+                    KeysIteratorFlags::SkipDuplicates,
+                ],
+                "",
+            )?
+            .collect()?;
+
+        for key_name in key_names {
+            let copied = source
+                .get_key_native_type(&key_name)
+                .and_then(|key_type| unsafe {
+                    codes_copy_key(
+                        source.message_handle,
+                        self.message_handle,
+                        &key_name,
+                        key_type,
+                    )
+                });
+
+            match copied {
+                Ok(()) => (),
+                Err(CodesError::Internal(
+                    err @ (CodesInternal::CodesReadOnly | CodesInternal::CodesNotFound),
+                )) => {
+                    debug!("Key {key_name} has not been copied: {err}");
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Re-encodes data values of the message, so that changed packing settings
+    /// (eg. `bitsPerValue` or `packingType`) take effect.
+    ///
+    /// ecCodes applies new packing settings only when `values` are set, so this function
+    /// reads current values and writes them back.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{CodesHandle, KeyWrite, ProductKind};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  message.write_key("bitsPerValue", 8)?;
+    ///  message.repack()?;
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`]
+    /// when internal ecCodes function returns non-zero code.
+    pub fn repack(&mut self) -> Result<(), CodesError> {
+        let values: Vec<f64> = self.read_key("values")?;
+        self.write_key("values", &values)?;
+
+        Ok(())
+    }
+
+    /// Returns a copy of the message converted to given GRIB edition.
+    ///
+    /// The message is cloned and `edition` key is set on the clone,
+    /// which makes ecCodes re-encode all sections in the new edition.
+    /// The original message is not modified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{CodesHandle, KeyRead, ProductKind};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let converted = message.to_edition(2)?;
+    ///  let edition: i64 = converted.read_key("edition")?;
+    ///  assert_eq!(edition, 2);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::CloneFailed`] when the message cannot be cloned.
+    ///
+    /// Returns [`CodesInternal::CodesDifferentEdition`]
+    /// or other [`CodesInternal`] error
+    /// when ecCodes cannot convert the message to requested edition.
+    pub fn to_edition(&self, edition: u8) -> Result<KeyedMessage, CodesError> {
+        let mut converted = self.try_clone()?;
+        converted.write_key("edition", i64::from(edition))?;
+
+        Ok(converted)
+    }
+
+    /// Sets the bitmap of the message, marking gridpoints where `mask` is `false` as missing.
+    ///
+    /// `bitmapPresent` is set to 1 and values at masked gridpoints are replaced with `missingValue`,
+    /// so that ecCodes encodes them as missing. `mask` is indexed in the same order as `values`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{CodesHandle, KeyRead, ProductKind};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  let number_of_values: i64 = message.read_key("numberOfValues")?;
+    ///  let mut mask = vec![true; usize::try_from(number_of_values)?];
+    ///  mask[0] = false;
+    ///
+    ///  message.apply_bitmap(&mask)?;
+    ///
+    ///  let missing: i64 = message.read_key("numberOfMissing")?;
+    ///  assert_eq!(missing, 1);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::WrongRequestedKeySize`] when length of `mask` is not equal
+    /// to the number of values in the message.
+    ///
+    /// Returns [`CodesInternal`]
+    /// when internal ecCodes function returns non-zero code.
+    pub fn apply_bitmap(&mut self, mask: &[bool]) -> Result<(), CodesError> {
+        let mut values: Vec<f64> = self.read_key("values")?;
+
+        if mask.len() != values.len() {
+            return Err(CodesError::WrongRequestedKeySize);
+        }
+
+        let missing_value: f64 = self.read_key_unchecked("missingValue")?;
+
+        for (value, _) in values
+            .iter_mut()
+            .zip(mask)
+            .filter(|(_, present)| !**present)
+        {
+            *value = missing_value;
+        }
+
+        self.write_key("bitmapPresent", 1)?;
+        self.write_key("values", &values)?;
+
+        Ok(())
+    }
+
+    /// Writes `values` of the message, encoding `None` entries as missing gridpoints.
+    ///
+    /// `bitmapPresent` is set to 1 before the values are written and `None` entries
+    /// are replaced with `missingValue`, so ecCodes marks them as missing in the bitmap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{CodesHandle, KeyRead, ProductKind};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  let values: Vec<f64> = message.read_key("values")?;
+    ///  let mut masked: Vec<Option<f64>> = values.into_iter().map(Some).collect();
+    ///  masked[0] = None;
+    ///
+    ///  message.set_values_masked(&masked)?;
+    ///
+    ///  let missing: i64 = message.read_key("numberOfMissing")?;
+    ///  assert_eq!(missing, 1);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`]
+    /// when internal ecCodes function returns non-zero code, eg. when the number
+    /// of values does not match the grid.
+    pub fn set_values_masked(&mut self, values: &[Option<f64>]) -> Result<(), CodesError> {
+        let missing_value: f64 = self.read_key_unchecked("missingValue")?;
+        let values: Vec<f64> = values
+            .iter()
+            .map(|value| value.unwrap_or(missing_value))
+            .collect();
+
+        self.write_key("bitmapPresent", 1)?;
+        self.write_key("values", &values)?;
+
+        Ok(())
+    }
+
+    /// Writes `values` of the message after clamping each of them into `[min, max]`.
+    ///
+    /// **This alters the data**: values below `min` are written as `min` and values above `max`
+    /// as `max`. It is intended for authoring messages where occasional spikes outside the range
+    /// representable with the chosen packing would otherwise make the whole write fail
+    /// with [`CodesInternal::CodesOutOfRange`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{CodesHandle, KeyRead, ProductKind};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let mut message = handle.next()?.context("no message")?.try_clone()?;
+    ///
+    ///  let values: Vec<f64> = message.read_key("values")?;
+    ///  message.set_values_clamped(&values, 95_000.0, 105_000.0)?;
+    ///
+    ///  let max: f64 = message.read_key("max")?;
+    ///  assert!(max <= 105_000.0);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`]
+    /// when internal ecCodes function returns non-zero code, eg. when the number
+    /// of values does not match the grid.
+    ///
+    /// Returns [`CodesError::InvalidClampRange`] when `min` is greater than `max`
+    /// or any of them is NaN. Nothing is written in that case.
+    pub fn set_values_clamped(
+        &mut self,
+        values: &[f64],
+        min: f64,
+        max: f64,
+    ) -> Result<(), CodesError> {
+        // f64::clamp() panics on such bounds
+        if min.is_nan() || max.is_nan() || min > max {
+            return Err(CodesError::InvalidClampRange { min, max });
+        }
+
+        let values: Vec<f64> = values.iter().map(|value| value.clamp(min, max)).collect();
+
+        self.write_key("values", &values)?;
+
+        Ok(())
+    }
+
+    /// Removes the bitmap from the message by setting `bitmapPresent` to 0.
+    ///
+    /// Gridpoints that were missing keep `missingValue` as their value.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesInternal`]
+    /// when internal ecCodes function returns non-zero code.
+    pub fn clear_bitmap(&mut self) -> Result<(), CodesError> {
+        self.write_key("bitmapPresent", 0)?;
+
+        Ok(())
+    }
+
+    /// Clones the message, applies `f` to the clone and returns the edited clone,
+    /// leaving the original message unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{CodesHandle, KeyRead, KeyWrite, ProductKind};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland-levels.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let new_message = message.rewrite_with(|m| {
+    ///      m.write_key("level", 850)?;
+    ///      Ok(())
+    ///  })?;
+    ///
+    ///  let level: i64 = new_message.read_key("level")?;
+    ///  assert_eq!(level, 850);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CodesError::CloneFailed`] when the message cannot be cloned
+    /// and any error returned by `f`.
+    pub fn rewrite_with<F>(&self, f: F) -> Result<KeyedMessage, CodesError>
+    where
+        F: FnOnce(&mut KeyedMessage) -> Result<(), CodesError>,
+    {
+        let mut new_message = self.try_clone()?;
+        f(&mut new_message)?;
+
+        Ok(new_message)
+    }
 }
 
 #[cfg(test)]
@@ -135,7 +694,9 @@ mod tests {
 
     use crate::{
         codes_handle::{CodesHandle, ProductKind},
-        DynamicKeyType, FallibleStreamingIterator, KeyWrite,
+        intermediate_bindings::{codes_get_message, codes_handle_new_from_samples},
+        CodesError, DynamicKeyType, FallibleStreamingIterator, KeyRead, KeyWrite, KeyedMessage,
+        NativeKeyType,
     };
     use std::{fs::remove_file, path::Path};
 
@@ -241,4 +802,285 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn resize_grid() -> Result<()> {
+        let product_kind = ProductKind::GRIB;
+        let file_path = Path::new("./data/iceland.grib");
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut current_message = handle.next()?.context("Message not some")?.try_clone()?;
+
+        let values: Vec<f64> = (0..50).map(f64::from).collect();
+        current_message.write_values_and_geometry(&values, 10, 5)?;
+
+        let ni: i64 = current_message.read_key("Ni")?;
+        let nj: i64 = current_message.read_key("Nj")?;
+        let number_of_values: i64 = current_message.read_key("numberOfValues")?;
+        let read_values: Vec<f64> = current_message.read_key("values")?;
+
+        assert_eq!(ni, 10);
+        assert_eq!(nj, 5);
+        assert_eq!(number_of_values, 50);
+        assert_eq!(read_values.len(), 50);
+
+        let result = current_message.write_values_and_geometry(&values, 7, 7);
+        assert!(matches!(result, Err(CodesError::WrongRequestedKeySize)));
+
+        let result = current_message.write_values_and_geometry(&values, i64::MAX, 2);
+        assert!(matches!(result, Err(CodesError::UnexpectedKeyValue(_))));
+
+        let ni: i64 = current_message.read_key("Ni")?;
+        assert_eq!(ni, 10);
+
+        Ok(())
+    }
+
+    #[test]
+    fn repack_message() -> Result<()> {
+        let product_kind = ProductKind::GRIB;
+        let file_path = Path::new("./data/iceland.grib");
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let mut current_message = handle.next()?.context("Message not some")?.try_clone()?;
+
+        let old_size = unsafe { codes_get_message(current_message.message_handle)?.1 };
+        let old_bits: i64 = current_message.read_key("bitsPerValue")?;
+
+        current_message.write_key("bitsPerValue", old_bits / 2)?;
+        current_message.repack()?;
+
+        let new_size = unsafe { codes_get_message(current_message.message_handle)?.1 };
+
+        assert!(new_size < old_size);
+
+        Ok(())
+    }
+
+    #[test]
+    fn convert_grib1_sample_to_grib2() -> Result<()> {
+        let message = KeyedMessage {
+            message_handle: unsafe { codes_handle_new_from_samples("GRIB1") },
+            keys_namespace: None,
+        };
+        assert!(!message.message_handle.is_null());
+
+        let converted = message.to_edition(2)?;
+
+        let original_edition: i64 = message.read_key("edition")?;
+        let converted_edition: i64 = converted.read_key("edition")?;
+
+        assert_eq!(original_edition, 1);
+        assert_eq!(converted_edition, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "message_ndarray")]
+    fn apply_and_clear_bitmap() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.context("Message not some")?.try_clone()?;
+
+        let values: Vec<f64> = msg.read_key("values")?;
+        let mut mask = vec![true; values.len()];
+        mask[0] = false;
+        mask[values.len() - 1] = false;
+
+        msg.apply_bitmap(&mask)?;
+
+        let bitmap = msg.bitmap()?.context("Bitmap not present")?;
+        assert_eq!(bitmap.iter().filter(|present| !**present).count(), 2);
+        assert!(!*bitmap.iter().next().context("Empty bitmap")?);
+        assert!(!*bitmap.iter().last().context("Empty bitmap")?);
+
+        msg.clear_bitmap()?;
+        assert!(msg.bitmap()?.is_none());
+
+        let wrong_mask = msg.apply_bitmap(&mask[1..]);
+        assert!(matches!(wrong_mask, Err(CodesError::WrongRequestedKeySize)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn masked_values_roundtrip() -> Result<()> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.context("Message not some")?.try_clone()?;
+
+        let values: Vec<f64> = msg.read_key("values")?;
+        let masked: Vec<Option<f64>> = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| (i % 7 != 0).then_some(*value))
+            .collect();
+
+        msg.set_values_masked(&masked)?;
+
+        let missing_value: f64 = msg.read_key("missingValue")?;
+        let read_back: Vec<Option<f64>> = msg
+            .values_iter(false)?
+            .map(|value| {
+                #[allow(clippy::float_cmp)]
+                // missing values are set by ecCodes to exactly `missingValue`
+                (value != missing_value).then_some(value)
+            })
+            .collect();
+
+        assert_eq!(read_back.len(), masked.len());
+        assert!(read_back
+            .iter()
+            .zip(&masked)
+            .all(|(read, written)| read.is_some() == written.is_some()));
+
+        let number_of_missing: i64 = msg.read_key("numberOfMissing")?;
+        assert_eq!(
+            usize::try_from(number_of_missing)?,
+            masked.iter().filter(|value| value.is_none()).count()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn clamped_values_roundtrip() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.context("Message not some")?.try_clone()?;
+
+        let mut values: Vec<f64> = msg.read_key("values")?;
+        values[0] = -1.0e9;
+        values[1] = 1.0e9;
+
+        msg.set_values_clamped(&values, 90_000.0, 110_000.0)?;
+
+        let read_back: Vec<f64> = msg.read_key("values")?;
+
+        assert!((read_back[0] - 90_000.0).abs() < 1.0);
+        assert!((read_back[1] - 110_000.0).abs() < 1.0);
+        assert!(read_back
+            .iter()
+            .all(|value| (90_000.0 - 1.0..=110_000.0 + 1.0).contains(value)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn clamped_values_invalid_range() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.context("Message not some")?.try_clone()?;
+
+        let values: Vec<f64> = msg.read_key("values")?;
+
+        for (min, max) in [(1.0, 0.0), (f64::NAN, 1.0), (0.0, f64::NAN)] {
+            assert!(matches!(
+                msg.set_values_clamped(&values, min, max),
+                Err(CodesError::InvalidClampRange { .. })
+            ));
+        }
+
+        let read_back: Vec<f64> = msg.read_key("values")?;
+        assert_eq!(read_back, values);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rewrite_with_keeps_original() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let msg = handle.next()?.context("Message not some")?;
+
+        let new_msg = msg.rewrite_with(|m| {
+            m.write_key("centre", "cnmc")?;
+            Ok(())
+        })?;
+
+        let new_centre: String = new_msg.read_key("centre")?;
+        let old_centre: String = msg.read_key("centre")?;
+        assert_eq!(new_centre, "cnmc");
+        assert_ne!(old_centre, "cnmc");
+
+        let failed = msg.rewrite_with(|m| {
+            m.write_key("nonExistingKey", 1)?;
+            Ok(())
+        });
+        assert!(failed.is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn replace_array_checked() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.context("Message not some")?.try_clone()?;
+
+        let values: Vec<f64> = msg.read_key("values")?;
+        let replaced = vec![1.0; values.len()];
+
+        msg.replace_array("values", &replaced)?;
+
+        let read_back: Vec<f64> = msg.read_key("values")?;
+        assert!(read_back.iter().all(|value| (value - 1.0).abs() < 1e-6));
+
+        let result = msg.replace_array("values", &replaced[1..]);
+        assert!(matches!(result, Err(CodesError::WrongRequestedKeySize)));
+
+        let unchanged: Vec<f64> = msg.read_key("values")?;
+        assert_eq!(unchanged.len(), values.len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn write_key_checked() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.context("Message not some")?.try_clone()?;
+
+        msg.write_key_checked("level", 850)?;
+        let level: i64 = msg.read_key("level")?;
+        assert_eq!(level, 850);
+
+        let result = msg.write_key_checked("level", 850.0);
+        assert!(matches!(
+            result,
+            Err(CodesError::WrongRequestedKeyType {
+                requested: "f64",
+                actual: NativeKeyType::Long,
+            })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn copy_metadata_onto_sample() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let source = handle.next()?.context("Message not some")?;
+
+        let edition: i64 = source.read_key("edition")?;
+        let mut sample = KeyedMessage {
+            message_handle: unsafe { codes_handle_new_from_samples(&format!("GRIB{edition}")) },
+            keys_namespace: None,
+        };
+        assert!(!sample.message_handle.is_null());
+
+        sample.copy_from(source)?;
+
+        for key in ["shortName", "Ni", "Nj", "dataDate", "dataTime"] {
+            assert_eq!(
+                sample.read_key_dynamic(key)?,
+                source.read_key_dynamic(key)?,
+                "{key} differs"
+            );
+        }
+
+        Ok(())
+    }
 }
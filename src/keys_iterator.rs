@@ -156,12 +156,18 @@ impl KeyedMessage {
     /// parameters: [`AllKeys`](KeysIteratorFlags::AllKeys) flag and `""` namespace,
     /// yeilding iterator over all keys in the message.
     ///
+    /// When the message was read from [`CodesHandle`](crate::CodesHandle) with namespace set by
+    /// [`set_default_keys_namespace()`](crate::CodesHandle::set_default_keys_namespace),
+    /// that namespace is used instead.
+    ///
     /// # Errors
     ///
     /// This function returns [`CodesInternal`](crate::errors::CodesInternal) when
     /// internal ecCodes function returns non-zero code.
     pub fn default_keys_iterator(&self) -> Result<KeysIterator, CodesError> {
-        let iterator_handle = unsafe { codes_keys_iterator_new(self.message_handle, 0, "")? };
+        let namespace = self.keys_namespace.as_deref().unwrap_or("");
+        let iterator_handle =
+            unsafe { codes_keys_iterator_new(self.message_handle, 0, namespace)? };
         let next_item_exists = unsafe { codes_keys_iterator_next(iterator_handle)? };
 
         Ok(KeysIterator {
@@ -170,6 +176,125 @@ impl KeyedMessage {
             next_item_exists,
         })
     }
+
+    /// Counts keys in the given namespace of the message without collecting their names.
+    ///
+    /// Empty namespace (`""`) counts all keys in the message. Invalid namespace results in count of 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let current_message = handle.next()?.context("no message")?;
+    ///
+    ///  let count = current_message.namespace_key_count("geography")?;
+    ///  println!("geography namespace contains {count} keys");
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`CodesInternal`](crate::errors::CodesInternal) when
+    /// internal ecCodes function returns non-zero code.
+    pub fn namespace_key_count(&self, namespace: &str) -> Result<usize, CodesError> {
+        self.new_keys_iterator(&[KeysIteratorFlags::AllKeys], namespace)?
+            .count()
+    }
+
+    /// Collects names of all keys in the message that are not read-only,
+    /// using [`SkipReadOnly`](KeysIteratorFlags::SkipReadOnly) flag.
+    ///
+    /// Note that writing a key listed here can still fail, if provided value is invalid
+    /// for the key or the message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let current_message = handle.next()?.context("no message")?;
+    ///
+    ///  let writable = current_message.writable_keys()?;
+    ///  assert!(writable.contains(&"level".to_string()));
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`CodesInternal`](crate::errors::CodesInternal) when
+    /// internal ecCodes function returns non-zero code.
+    pub fn writable_keys(&self) -> Result<Vec<String>, CodesError> {
+        self.new_keys_iterator(&[KeysIteratorFlags::SkipReadOnly], "")?
+            .collect()
+    }
+
+    /// Checks whether the key is listed in [`writable_keys()`](KeyedMessage::writable_keys).
+    ///
+    /// Returns `false` also for keys that are not present in the message.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`CodesInternal`](crate::errors::CodesInternal) when
+    /// internal ecCodes function returns non-zero code.
+    pub fn is_key_writable(&self, key_name: &str) -> Result<bool, CodesError> {
+        self.new_keys_iterator(&[KeysIteratorFlags::SkipReadOnly], "")?
+            .any(|name| Ok(name == key_name))
+    }
+}
+
+impl KeysIterator<'_> {
+    /// Advances the iterator past the key with given name, so that next call to `next()`
+    /// returns the key following it. Useful for resuming iteration from a known key.
+    ///
+    /// Returns `true` if the key has been found or `false` if the iterator has been exhausted without finding it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::{FallibleIterator, FallibleStreamingIterator};
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let current_message = handle.next()?.context("no message")?;
+    ///
+    ///  let mut keys_iter = current_message.default_keys_iterator()?;
+    ///
+    ///  if keys_iter.skip_to("shortName")? {
+    ///      let following_key = keys_iter.next()?;
+    ///  }
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`CodesInternal`](crate::errors::CodesInternal) when
+    /// internal ecCodes function returns non-zero code.
+    pub fn skip_to(&mut self, name: &str) -> Result<bool, CodesError> {
+        while let Some(key_name) = self.next()? {
+            if key_name == name {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
 }
 
 impl FallibleIterator for KeysIterator<'_> {
@@ -269,8 +394,129 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn namespace_count() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        let geography_count = current_message.namespace_key_count("geography")?;
+        let all_count = current_message.namespace_key_count("")?;
+
+        assert!(geography_count > 0);
+        assert!(all_count > geography_count);
+        assert_eq!(current_message.namespace_key_count("blabla")?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn handle_default_namespace() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        handle.set_default_keys_namespace("parameter");
+        let current_message = handle.next()?.context("Message not some")?;
+
+        let expected: Vec<String> = current_message
+            .new_keys_iterator(&[KeysIteratorFlags::AllKeys], "parameter")?
+            .collect()?;
+        let keys: Vec<String> = current_message.default_keys_iterator()?.collect()?;
+        let cloned_keys: Vec<String> = current_message
+            .try_clone()?
+            .default_keys_iterator()?
+            .collect()?;
+
+        assert!(!keys.is_empty());
+        assert_eq!(keys, expected);
+        assert_eq!(cloned_keys, expected);
+        assert!(keys.len() < current_message.namespace_key_count("")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn writable_keys() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        let writable = current_message.writable_keys()?;
+        let all: Vec<String> = current_message.default_keys_iterator()?.collect()?;
+
+        assert!(!writable.is_empty());
+        assert!(writable.len() < all.len());
+        assert!(current_message.is_key_writable("level")?);
+        assert!(!current_message.is_key_writable("doesNotExist")?);
+
+        let read_only = all
+            .iter()
+            .find(|name| !writable.contains(name))
+            .context("No read-only key")?;
+        assert!(!current_message.is_key_writable(read_only)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reiterate_keys() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        // iterator state lives in KeysIterator, so re-iterating only requires a new iterator
+        let first_pass: Vec<String> = current_message.default_keys_iterator()?.collect()?;
+        let second_pass: Vec<String> = current_message.default_keys_iterator()?.collect()?;
+
+        assert!(!first_pass.is_empty());
+        assert_eq!(first_pass, second_pass);
+
+        Ok(())
+    }
+
+    #[test]
+    fn skip_to_key() -> Result<()> {
+        let file_path = Path::new("./data/iceland.grib");
+        let product_kind = ProductKind::GRIB;
+
+        let mut handle = CodesHandle::new_from_file(file_path, product_kind)?;
+        let current_message = handle.next()?.context("Message not some")?;
+
+        let all_keys: Vec<String> = current_message.default_keys_iterator()?.collect()?;
+        let position = all_keys
+            .iter()
+            .position(|name| name == "shortName")
+            .context("No shortName key")?;
+
+        let mut kiter = current_message.default_keys_iterator()?;
+        assert!(kiter.skip_to("shortName")?);
+
+        let following: Vec<String> = kiter.by_ref().take(3).collect()?;
+        let expected: Vec<String> = all_keys
+            .iter()
+            .skip(position + 1)
+            .take(3)
+            .cloned()
+            .collect();
+        assert_eq!(following, expected);
+
+        assert!(!kiter.skip_to("shortName")?);
+        assert_eq!(kiter.next()?, None);
+
+        Ok(())
+    }
+
     #[test]
     fn destructor() -> Result<()> {
+        testing_logger::setup();
+
         let file_path = Path::new("./data/iceland.grib");
         let product_kind = ProductKind::GRIB;
 
@@ -64,6 +64,21 @@
 //!   **This feature is experimental** and disabled by default. If you want to use it, please read
 //!   the information provided in [`codes_index`] documentation.
 //!
+//! - `flate2` - enables opening gzip-compressed GRIB files with
+//!   [`new_from_gzip_file()`](CodesHandle::new_from_gzip_file). This feature is disabled by default.
+//!
+//! - `memmap2` - enables accessing GRIB files through memory map with
+//!   [`new_from_mmap()`](CodesHandle::new_from_mmap). This feature is disabled by default.
+//!
+//! - `chrono` - enables setting and reading message date and time with [`chrono`] types.
+//!   This feature is disabled by default.
+//!
+//! - `futures` - enables iterating over messages as a [`Stream`](futures_core::Stream) with
+//!   [`into_message_stream()`](CodesHandle::into_message_stream). This feature is disabled by default.
+//!
+//! - `serde` - enables serializing messages converted with
+//!   [`to_field_record()`](KeyedMessage::to_field_record) (requires also `message_ndarray`). This feature is disabled by default.
+//!
 //! - `docs` - builds the crate without linking ecCodes, particularly useful when building the documentation
 //!   on [docs.rs](https://docs.rs/). For more details check documentation of [eccodes-sys](https://crates.io/crates/eccodes-sys).
 //!
@@ -197,8 +212,9 @@
 //!     .collect();
 //!
 //! // Edit appropriate keys in the editable message
-//! new_msg.write_key("level", 850)?;
-//! new_msg.write_key("values", &t850)?;
+//! new_msg
+//!     .write_key("level", 850)?
+//!     .write_key("values", &t850)?;
 //!
 //! // Save the message to a new file without appending
 //! new_msg.write_to_file(Path::new("iceland-850.grib"), false)?;
@@ -209,11 +225,13 @@
 //! ```
 //!
 
+pub mod codes_context;
 pub mod codes_handle;
 #[cfg(feature = "experimental_index")]
 #[cfg_attr(docsrs, doc(cfg(feature = "experimental_index")))]
 pub mod codes_index;
 pub mod codes_nearest;
+pub mod compare;
 pub mod errors;
 mod intermediate_bindings;
 pub mod keyed_message;
@@ -223,13 +241,18 @@ pub mod keys_iterator;
 pub mod message_ndarray;
 mod pointer_guard;
 
-pub use codes_handle::{CodesHandle, ProductKind};
+pub use codes_context::{available_samples, CodesContext};
+pub use codes_handle::{CodesHandle, OpenMode, ProductKind, ValidationReport};
 #[cfg(feature = "experimental_index")]
 #[cfg_attr(docsrs, doc(cfg(feature = "experimental_index")))]
-pub use codes_index::CodesIndex;
-pub use codes_nearest::{CodesNearest, NearestGridpoint};
+pub use codes_index::{build_index, CodesIndex};
+pub use codes_nearest::{CodesNearest, CodesNearestCache, NearestGridpoint, NeighbourQuad};
+pub use compare::{compare_files, FileDiff, KeyDiff};
 pub use errors::CodesError;
 pub use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
 pub use fallible_streaming_iterator::FallibleStreamingIterator;
-pub use keyed_message::{DynamicKeyType, KeyRead, KeyWrite, KeyedMessage};
+pub use keyed_message::{
+    DynamicKeyType, GridDefinition, GridType, Interpolation, KeyRead, KeyWrite, KeyedMessage,
+    Level, NativeKeyType, ProcessedDataType, ProductionStatus,
+};
 pub use keys_iterator::{KeysIterator, KeysIteratorFlags};
@@ -1,9 +1,11 @@
 #![cfg_attr(docsrs, doc(cfg(feature = "message_ndarray")))]
 //! Definitions for converting a `KeyedMessage` to ndarray
 
-use ndarray::{s, Array2, Array3};
+use ndarray::{Array2, ErrorKind, ShapeError};
 
-use crate::{errors::MessageNdarrayError, CodesError, KeyRead, KeyedMessage};
+#[cfg(feature = "serde")]
+use crate::DynamicKeyType;
+use crate::{errors::MessageNdarrayError, CodesError, KeyRead, KeyWrite, KeyedMessage};
 
 /// Struct returned by [`KeyedMessage::to_lons_lats_values()`] method.
 /// The arrays are collocated, meaning that `longitudes[i, j]` and `latitudes[i, j]` are the coordinates of `values[i, j]`.
@@ -18,6 +20,33 @@ pub struct RustyCodesMessage {
     pub values: Array2<f64>,
 }
 
+/// Struct returned by [`KeyedMessage::to_field_record()`] method, containing all data
+/// needed to reconstruct the field in other tools (eg. as xarray `DataArray`).
+///
+/// Coordinates are ordered the same way as rows and columns of `values`,
+/// so `values[[j, i]]` is located at `latitudes[j]` and `longitudes[i]`.
+#[cfg(feature = "serde")]
+#[derive(Clone, PartialEq, Debug, serde::Serialize)]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "message_ndarray", feature = "serde"))))]
+pub struct FieldRecord {
+    /// Values shaped as in [`KeyedMessage::to_ndarray()`]
+    pub values: Array2<f64>,
+    /// Latitudes of grid rows in degrees
+    pub latitudes: Vec<f64>,
+    /// Longitudes of grid columns in degrees
+    pub longitudes: Vec<f64>,
+    /// Units of values (`units` key)
+    pub units: String,
+    /// Short name of the parameter (`shortName` key)
+    pub short_name: String,
+    /// CF standard name of the parameter (`cfName` key), if defined
+    pub cf_name: Option<String>,
+    /// Validity date as `YYYYMMDD` (`validityDate` key)
+    pub validity_date: i64,
+    /// Validity time as `HHMM` (`validityTime` key)
+    pub validity_time: i64,
+}
+
 impl KeyedMessage {
     /// Converts the message to a 2D ndarray.
     ///
@@ -34,50 +63,121 @@ impl KeyedMessage {
     ///
     /// Requires the keys `Ni`, `Nj` and `values` to be present in the message.
     ///
+    /// When the message contains a bitmap and ecCodes returns values only for present gridpoints,
+    /// the values are expanded to the full grid using the bitmap and missing gridpoints are set to `missingValue`.
+    ///
+    /// Values are already physically scaled: ecCodes applies `referenceValue`, `binaryScaleFactor`
+    /// and `decimalScaleFactor` when decoding the `values` key, so no further scaling is needed.
+    ///
     /// Tested only with simple lat-lon grids.
     ///
     /// # Errors
     ///
     /// - When the required keys are not present or if their values are not of the expected type
     /// - When the number of values mismatch with the `Ni` and `Nj` keys
+    /// - When the number of values mismatch with the number of gridpoints present in the bitmap
+    /// - When gridpoints are staggered or rows are scanned alternately, as such grids
+    ///   cannot be represented without shifting values ([`MessageNdarrayError::UnsupportedGrid`])
     #[cfg_attr(docsrs, doc(cfg(feature = "message_ndarray")))]
     pub fn to_ndarray(&self) -> Result<Array2<f64>, CodesError> {
-        let ni: i64 = self.read_key("Ni")?;
-        let ni = usize::try_from(ni).map_err(MessageNdarrayError::from)?;
-
-        let nj: i64 = self.read_key("Nj")?;
-        let nj = usize::try_from(nj).map_err(MessageNdarrayError::from)?;
+        let (ni, nj, j_scanning) = self.ndarray_grid_shape()?;
 
         let vals: Vec<f64> = self.read_key("values")?;
-        if vals.len() != (ni * nj) {
-            return Err(MessageNdarrayError::UnexpectedValuesLength(vals.len(), ni * nj).into());
-        }
+        let vals = self.expand_bitmapped_values(vals, ni * nj)?;
 
-        let j_scanning: i64 = self.read_key("jPointsAreConsecutive")?;
+        grid_array(vals, ni, nj, j_scanning)
+    }
 
-        if ![0, 1].contains(&j_scanning) {
-            return Err(MessageNdarrayError::UnexpectedKeyValue(
-                "jPointsAreConsecutive".to_owned(),
-            )
-            .into());
-        }
+    /// Same as [`KeyedMessage::to_ndarray()`] but for integer-valued fields (eg. land-sea type codes),
+    /// reading `values` as a long array.
+    ///
+    /// Conversion of values to integers is done by ecCodes, which truncates fractional part,
+    /// so this method should be used only with fields containing integer values.
+    ///
+    /// # Errors
+    ///
+    /// - When the required keys are not present or if their values are not of the expected type
+    /// - When the number of values mismatch with the `Ni` and `Nj` keys
+    #[cfg_attr(docsrs, doc(cfg(feature = "message_ndarray")))]
+    pub fn to_ndarray_i64(&self) -> Result<Array2<i64>, CodesError> {
+        let (ni, nj, j_scanning) = self.ndarray_grid_shape()?;
 
-        let j_scanning = j_scanning != 0;
+        let vals: Vec<i64> = self.read_key_unchecked("values")?;
 
-        let shape = if j_scanning { (ni, nj) } else { (nj, ni) };
-        let vals = Array2::from_shape_vec(shape, vals).map_err(MessageNdarrayError::from)?;
+        grid_array(vals, ni, nj, j_scanning)
+    }
 
-        if j_scanning {
-            Ok(vals.reversed_axes())
-        } else {
-            Ok(vals)
+    /// Returns the bitmap of the message as a 2D ndarray, where `true` indicates
+    /// gridpoints with present values and `false` gridpoints with missing values.
+    ///
+    /// Returns `None` when the message does not contain a bitmap (`bitmapPresent` is 0).
+    ///
+    /// Indexing convention is the same as in [`KeyedMessage::to_ndarray()`],
+    /// so the bitmap can be used directly to mask the values array.
+    ///
+    /// # Errors
+    ///
+    /// - When the required keys are not present or if their values are not of the expected type
+    /// - When the length of the bitmap mismatch with the `Ni` and `Nj` keys
+    #[cfg_attr(docsrs, doc(cfg(feature = "message_ndarray")))]
+    pub fn bitmap(&self) -> Result<Option<Array2<bool>>, CodesError> {
+        let bitmap_present: bool = self.read_key("bitmapPresent")?;
+
+        if !bitmap_present {
+            return Ok(None);
         }
+
+        let (ni, nj, j_scanning) = self.ndarray_grid_shape()?;
+
+        let bitmap: Vec<i64> = self.read_key("bitmap")?;
+        let bitmap = bitmap.into_iter().map(|bit| bit != 0).collect();
+
+        grid_array(bitmap, ni, nj, j_scanning).map(Some)
+    }
+
+    /// Writes the 2D array to an array key of the message, eg. `values` or a custom `bitmap`.
+    ///
+    /// The array must have the `[lat, lon]` shape of the grid, with the same indexing convention
+    /// as in [`KeyedMessage::to_ndarray()`]. It is flattened in the scanning order of the message
+    /// (respecting `jPointsAreConsecutive`) before being written.
+    ///
+    /// # Errors
+    ///
+    /// - When the required keys are not present or if their values are not of the expected type
+    /// - When the shape of the array is different than `(Nj, Ni)`
+    /// - When the key cannot be written
+    #[cfg_attr(docsrs, doc(cfg(feature = "message_ndarray")))]
+    pub fn write_key_ndarray(&mut self, name: &str, arr: &Array2<f64>) -> Result<(), CodesError> {
+        let vals = self.scan_order_vec(arr)?;
+        self.write_key(name, vals.as_slice())?;
+
+        Ok(())
+    }
+
+    /// Same as [`KeyedMessage::write_key_ndarray()`] but for long array keys, eg. `bitmap`.
+    ///
+    /// # Errors
+    ///
+    /// - When the required keys are not present or if their values are not of the expected type
+    /// - When the shape of the array is different than `(Nj, Ni)`
+    /// - When the key cannot be written
+    #[cfg_attr(docsrs, doc(cfg(feature = "message_ndarray")))]
+    pub fn write_key_ndarray_i64(
+        &mut self,
+        name: &str,
+        arr: &Array2<i64>,
+    ) -> Result<(), CodesError> {
+        let vals = self.scan_order_vec(arr)?;
+        self.write_key(name, vals.as_slice())?;
+
+        Ok(())
     }
 
     /// Same as [`KeyedMessage::to_ndarray()`] but returns the longitudes and latitudes alongside values.
     /// Fields are returned as separate arrays in [`RustyCodesMessage`].
     ///
-    /// Compared to `to_ndarray` this method has performance overhead as returned arrays may be cloned.
+    /// Compared to `to_ndarray` this method has performance overhead, as coordinates
+    /// are read from ecCodes and split from values into separate arrays.
     ///
     /// This method requires the `latLonValues`, `Ni` and `Nj` keys to be present in the message.
     ///
@@ -87,11 +187,7 @@ impl KeyedMessage {
     /// - When the number of values mismatch with the `Ni` and `Nj` keys
     #[cfg_attr(docsrs, doc(cfg(feature = "message_ndarray")))]
     pub fn to_lons_lats_values(&self) -> Result<RustyCodesMessage, CodesError> {
-        let ni: i64 = self.read_key("Ni")?;
-        let ni = usize::try_from(ni).map_err(MessageNdarrayError::from)?;
-
-        let nj: i64 = self.read_key("Nj")?;
-        let nj = usize::try_from(nj).map_err(MessageNdarrayError::from)?;
+        let (ni, nj, j_scanning) = self.ndarray_grid_shape()?;
 
         let latlonvals: Vec<f64> = self.read_key("latLonValues")?;
 
@@ -101,6 +197,234 @@ impl KeyedMessage {
             );
         }
 
+        // split (lat, lon, value) triples into separate vectors in one pass,
+        // so each array owns its vector and no strided view has to be copied
+        let mut lats = Vec::with_capacity(ni * nj);
+        let mut lons = Vec::with_capacity(ni * nj);
+        let mut vals = Vec::with_capacity(ni * nj);
+
+        for point in latlonvals.chunks_exact(3) {
+            lats.push(point[0]);
+            lons.push(point[1]);
+            vals.push(point[2]);
+        }
+
+        Ok(RustyCodesMessage {
+            longitudes: grid_array(lons, ni, nj, j_scanning)?,
+            latitudes: grid_array(lats, ni, nj, j_scanning)?,
+            values: grid_array(vals, ni, nj, j_scanning)?,
+        })
+    }
+
+    /// Collects shaped values, coordinates and metadata of the message into serializable [`FieldRecord`].
+    ///
+    /// Coordinates are read from `distinctLatitudes` and `distinctLongitudes` keys
+    /// and ordered to match the direction of the grid scanning, so they are aligned with values.
+    ///
+    /// Requires regular grid, as coordinates of other grids cannot be represented by two vectors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let record = message.to_field_record()?;
+    ///  assert_eq!(record.values.shape(), [record.latitudes.len(), record.longitudes.len()]);
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// - When any of the required keys is not present or cannot be read
+    /// - When the number of values mismatch with the `Ni` and `Nj` keys
+    /// - When the number of distinct coordinates mismatch with the shape of values
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "message_ndarray", feature = "serde"))))]
+    pub fn to_field_record(&self) -> Result<FieldRecord, CodesError> {
+        let values = self.to_ndarray()?;
+
+        let mut latitudes: Vec<f64> = self.read_key("distinctLatitudes")?;
+        let mut longitudes: Vec<f64> = self.read_key("distinctLongitudes")?;
+
+        // ecCodes returns distinct coordinates in ascending order
+        let j_scans_positively: bool = self.read_key("jScansPositively")?;
+        let i_scans_negatively: bool = self.read_key("iScansNegatively")?;
+
+        if !j_scans_positively {
+            latitudes.reverse();
+        }
+
+        if i_scans_negatively {
+            longitudes.reverse();
+        }
+
+        if values.shape() != [latitudes.len(), longitudes.len()] {
+            return Err(MessageNdarrayError::UnexpectedValuesLength(
+                values.len(),
+                latitudes.len() * longitudes.len(),
+            )
+            .into());
+        }
+
+        let cf_name = match self.read_key_dynamic_if_present("cfName")? {
+            Some(DynamicKeyType::Str(name)) if name != "unknown" => Some(name),
+            _ => None,
+        };
+
+        Ok(FieldRecord {
+            values,
+            latitudes,
+            longitudes,
+            units: self.read_key("units")?,
+            short_name: self.read_key("shortName")?,
+            cf_name,
+            validity_date: self.read_key("validityDate")?,
+            validity_time: self.read_key("validityTime")?,
+        })
+    }
+
+    /// Returns values of gridpoints located inside the latitude-longitude bounding box (inclusive),
+    /// eg. to compute regional statistics.
+    ///
+    /// Longitudes can be given in any convention (eg. `-180..180` or `0..360`), as they are normalised
+    /// together with gridpoint longitudes. When `min_lon` is greater than `max_lon` (after normalisation)
+    /// the box is assumed to cross the antimeridian, so `170.0..-170.0` selects a 20 degree wide box.
+    ///
+    /// Values are returned in the order of [`KeyedMessage::to_lons_lats_values()`] arrays.
+    ///
+    /// # Example
+    ///
+    /// ```
+    ///  use eccodes::{ProductKind, CodesHandle};
+    ///  # use std::path::Path;
+    ///  # use anyhow::Context;
+    ///  use eccodes::FallibleStreamingIterator;
+    ///  #
+    ///  # fn main() -> anyhow::Result<()> {
+    ///  let mut handle = CodesHandle::new_from_file(Path::new("./data/iceland.grib"), ProductKind::GRIB)?;
+    ///  let message = handle.next()?.context("no message")?;
+    ///
+    ///  let values = message.values_in_bbox(64.0, 65.0, -22.0, -20.0)?;
+    ///  let mean = values.iter().sum::<f64>() / values.len() as f64;
+    ///  # Ok(())
+    ///  # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Same as [`KeyedMessage::to_lons_lats_values()`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "message_ndarray")))]
+    pub fn values_in_bbox(
+        &self,
+        min_lat: f64,
+        max_lat: f64,
+        min_lon: f64,
+        max_lon: f64,
+    ) -> Result<Vec<f64>, CodesError> {
+        let rmsg = self.to_lons_lats_values()?;
+
+        let whole_globe = max_lon - min_lon >= 360.0;
+        let min_lon = normalize_longitude(min_lon);
+        let max_lon = normalize_longitude(max_lon);
+
+        let lon_in_box = |lon: f64| {
+            let lon = normalize_longitude(lon);
+
+            if whole_globe {
+                true
+            } else if min_lon <= max_lon {
+                (min_lon..=max_lon).contains(&lon)
+            } else {
+                lon >= min_lon || lon <= max_lon
+            }
+        };
+
+        Ok(rmsg
+            .values
+            .iter()
+            .zip(rmsg.latitudes.iter())
+            .zip(rmsg.longitudes.iter())
+            .filter(|((_, lat), lon)| (min_lat..=max_lat).contains(*lat) && lon_in_box(**lon))
+            .map(|((val, _), _)| *val)
+            .collect())
+    }
+
+    /// Expands values of a bitmapped message, which contain only gridpoints present in the bitmap,
+    /// to `grid_len` values with missing gridpoints set to `missingValue`.
+    ///
+    /// Values already matching the grid size or from messages without bitmap are returned unchanged.
+    fn expand_bitmapped_values(
+        &self,
+        vals: Vec<f64>,
+        grid_len: usize,
+    ) -> Result<Vec<f64>, CodesError> {
+        if vals.len() == grid_len {
+            return Ok(vals);
+        }
+
+        let bitmap_present: bool = self.read_key("bitmapPresent")?;
+
+        if !bitmap_present {
+            return Ok(vals);
+        }
+
+        let bitmap: Vec<i64> = self.read_key("bitmap")?;
+        let present_count = bitmap.iter().filter(|bit| **bit != 0).count();
+
+        if vals.len() != present_count {
+            return Err(MessageNdarrayError::UnexpectedBitmapValuesLength(
+                vals.len(),
+                present_count,
+            )
+            .into());
+        }
+
+        let missing_value: f64 = self.read_key_unchecked("missingValue")?;
+        let mut present_vals = vals.into_iter();
+
+        Ok(bitmap
+            .into_iter()
+            .map(|bit| match bit {
+                0 => missing_value,
+                _ => present_vals.next().unwrap_or(missing_value),
+            })
+            .collect())
+    }
+
+    /// Flattens `[lat, lon]` array into the scanning order of the message, reversing [`grid_array()`].
+    fn scan_order_vec<T: Copy>(&self, arr: &Array2<T>) -> Result<Vec<T>, CodesError> {
+        let (ni, nj, j_scanning) = self.ndarray_grid_shape()?;
+
+        if arr.dim() != (nj, ni) {
+            return Err(MessageNdarrayError::from(ShapeError::from_kind(
+                ErrorKind::IncompatibleShape,
+            ))
+            .into());
+        }
+
+        if j_scanning {
+            Ok(arr.t().iter().copied().collect())
+        } else {
+            Ok(arr.iter().copied().collect())
+        }
+    }
+
+    /// Reads `Ni`, `Nj` and `jPointsAreConsecutive` keys used to shape the arrays.
+    fn ndarray_grid_shape(&self) -> Result<(usize, usize, bool), CodesError> {
+        let ni: i64 = self.read_key("Ni")?;
+        let ni = usize::try_from(ni).map_err(MessageNdarrayError::from)?;
+
+        let nj: i64 = self.read_key("Nj")?;
+        let nj = usize::try_from(nj).map_err(MessageNdarrayError::from)?;
+
         let j_scanning: i64 = self.read_key("jPointsAreConsecutive")?;
 
         if ![0, 1].contains(&j_scanning) {
@@ -110,31 +434,57 @@ impl KeyedMessage {
             .into());
         }
 
-        let j_scanning = j_scanning != 0;
-
-        let shape = if j_scanning {
-            (ni, nj, 3_usize)
-        } else {
-            (nj, ni, 3_usize)
-        };
+        // bit 4 of the scanning mode flags (WMO flag table 3.4) describes boustrophedonic rows
+        // and bits 5-8 describe gridpoints offset by half a grid length
+        let scanning_mode: i64 = self.read_key("scanningMode")?;
 
-        let mut latlonvals =
-            Array3::from_shape_vec(shape, latlonvals).map_err(MessageNdarrayError::from)?;
+        if scanning_mode & ALTERNATIVE_ROW_SCANNING != 0 {
+            return Err(
+                MessageNdarrayError::UnsupportedGrid("rows are scanned alternately").into(),
+            );
+        }
 
-        if j_scanning {
-            latlonvals.swap_axes(0, 1);
+        if scanning_mode & STAGGERED_POINTS != 0 {
+            return Err(MessageNdarrayError::UnsupportedGrid(
+                "gridpoints are staggered (offset by half a grid length)",
+            )
+            .into());
         }
 
-        let (lats, lons, vals) =
-            latlonvals
-                .view_mut()
-                .multi_slice_move((s![.., .., 0], s![.., .., 1], s![.., .., 2]));
+        Ok((ni, nj, j_scanning != 0))
+    }
+}
+
+/// Bit 4 of `scanningMode`, set when adjacent rows are scanned in opposite directions
+const ALTERNATIVE_ROW_SCANNING: i64 = 0b0001_0000;
 
-        Ok(RustyCodesMessage {
-            longitudes: lons.into_owned(),
-            latitudes: lats.into_owned(),
-            values: vals.into_owned(),
-        })
+/// Bits 5-8 of `scanningMode`, set when points in odd or even rows are offset
+/// or rows have one point less
+const STAGGERED_POINTS: i64 = 0b0000_1111;
+
+/// Maps longitude in degrees to `-180..180` range.
+fn normalize_longitude(lon: f64) -> f64 {
+    (lon + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Reshapes values read from the message into `[lat, lon]` array, as described in [`KeyedMessage::to_ndarray()`].
+fn grid_array<T>(
+    vals: Vec<T>,
+    ni: usize,
+    nj: usize,
+    j_scanning: bool,
+) -> Result<Array2<T>, CodesError> {
+    if vals.len() != (ni * nj) {
+        return Err(MessageNdarrayError::UnexpectedValuesLength(vals.len(), ni * nj).into());
+    }
+
+    let shape = if j_scanning { (ni, nj) } else { (nj, ni) };
+    let vals = Array2::from_shape_vec(shape, vals).map_err(MessageNdarrayError::from)?;
+
+    if j_scanning {
+        Ok(vals.reversed_axes())
+    } else {
+        Ok(vals)
     }
 }
 
@@ -175,6 +525,222 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_staggered_grid_guard() -> Result<(), CodesError> {
+        use crate::KeyWrite;
+
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.ok_or(CodesError::MissingKey)?.try_clone()?;
+
+        let scanning_mode: i64 = msg.read_key("scanningMode")?;
+
+        // bits 4-8 of WMO flag table 3.4
+        for flag in [
+            0b0001_0000,
+            0b0000_1000,
+            0b0000_0100,
+            0b0000_0010,
+            0b0000_0001,
+        ] {
+            msg.write_key("scanningMode", scanning_mode | flag)?;
+
+            let Err(CodesError::NdarrayConvert(MessageNdarrayError::UnsupportedGrid(reason))) =
+                msg.to_ndarray()
+            else {
+                panic!("flag {flag:#010b} not rejected");
+            };
+
+            assert_eq!(
+                reason.contains("alternately"),
+                flag == ALTERNATIVE_ROW_SCANNING,
+                "flag {flag:#010b} rejected as: {reason}"
+            );
+        }
+
+        msg.write_key("scanningMode", scanning_mode)?;
+        assert!(msg.to_ndarray().is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_ndarray_i64() -> Result<(), CodesError> {
+        use crate::KeyWrite;
+
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.ok_or(CodesError::MissingKey)?.try_clone()?;
+
+        let values: Vec<f64> = msg.read_key("values")?;
+        msg.write_key("values", &vec![3.0; values.len()])?;
+
+        let ndarray = msg.to_ndarray_i64()?;
+
+        assert_eq!(ndarray.shape(), msg.to_ndarray()?.shape());
+        assert_eq!(ndarray[[5, 5]], 3);
+        assert!(ndarray.iter().all(|value| *value == 3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bitmap() -> Result<(), CodesError> {
+        use crate::KeyWrite;
+
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.ok_or(CodesError::MissingKey)?.try_clone()?;
+
+        assert!(msg.bitmap()?.is_none());
+
+        let missing_value = 9999.0;
+        let mut values: Vec<f64> = msg.read_key("values")?;
+        values[0] = missing_value;
+
+        msg.write_key("bitmapPresent", 1)?
+            .write_key("missingValue", missing_value)?
+            .write_key("values", &values)?;
+
+        let bitmap = msg.bitmap()?.ok_or(CodesError::MissingKey)?;
+        let ndarray = msg.to_ndarray()?;
+
+        assert_eq!(bitmap.shape(), ndarray.shape());
+        assert!(!bitmap[[0, 0]]);
+        assert_eq!(bitmap.iter().filter(|present| !**present).count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_key_ndarray() -> Result<(), CodesError> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.ok_or(CodesError::MissingKey)?.try_clone()?;
+
+        let mut bitmap = Array2::<i64>::ones((17, 49));
+        bitmap[[0, 0]] = 0;
+        bitmap[[3, 40]] = 0;
+        bitmap[[16, 5]] = 0;
+
+        msg.write_key("bitmapPresent", 1)?;
+        msg.write_key_ndarray_i64("bitmap", &bitmap)?;
+
+        let read_back = msg.bitmap()?.ok_or(CodesError::MissingKey)?;
+        assert_eq!(read_back, bitmap.mapv(|bit| bit != 0));
+
+        let values = msg.to_ndarray()?.mapv(|value| value + 1.0);
+        msg.write_key("bitmapPresent", 0)?;
+        msg.write_key_ndarray("values", &values)?;
+
+        let read_back = msg.to_ndarray()?;
+        assert_approx_eq!(f64, read_back[[5, 5]], values[[5, 5]], epsilon = 0.01);
+        assert_approx_eq!(f64, read_back[[8, 37]], values[[8, 37]], epsilon = 0.01);
+
+        let transposed = values.t().to_owned();
+        assert!(matches!(
+            msg.write_key_ndarray("values", &transposed),
+            Err(CodesError::NdarrayConvert(
+                MessageNdarrayError::InvalidShape(_)
+            ))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_expand_bitmapped_values() -> Result<(), CodesError> {
+        use crate::KeyWrite;
+
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let mut msg = handle.next()?.ok_or(CodesError::MissingKey)?.try_clone()?;
+
+        let missing_value = 9999.0;
+        let mut values: Vec<f64> = msg.read_key("values")?;
+        values[0] = missing_value;
+        values[10] = missing_value;
+
+        msg.write_key("bitmapPresent", 1)?
+            .write_key("missingValue", missing_value)?
+            .write_key("values", &values)?;
+
+        // codedValues contain only gridpoints present in the bitmap
+        let coded_values: Vec<f64> = msg.read_key("codedValues")?;
+        assert_eq!(coded_values.len(), values.len() - 2);
+
+        let expanded = msg.expand_bitmapped_values(coded_values, values.len())?;
+        let full_values: Vec<f64> = msg.read_key("values")?;
+
+        assert_eq!(expanded.len(), values.len());
+        assert_approx_eq!(f64, expanded[0], missing_value);
+        assert_approx_eq!(f64, expanded[10], missing_value);
+        assert_eq!(expanded, full_values);
+
+        let result = msg.expand_bitmapped_values(vec![0.0; 5], values.len());
+        assert!(matches!(
+            result,
+            Err(CodesError::NdarrayConvert(
+                MessageNdarrayError::UnexpectedBitmapValuesLength(5, _)
+            ))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_values_in_bbox() -> Result<(), CodesError> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+        let msg = handle.next()?.ok_or(CodesError::MissingKey)?;
+
+        // grid spans 63..67 N and 25..13 W with 0.25 degree resolution
+        let values = msg.values_in_bbox(63.5, 66.5, -24.0, -14.0)?;
+        assert_eq!(values.len(), 13 * 41);
+
+        let all_values = msg.values_in_bbox(63.0, 67.0, 335.0, 347.0)?;
+        assert_eq!(all_values.len(), 17 * 49);
+
+        let across_antimeridian = msg.values_in_bbox(63.0, 67.0, 170.0, -170.0)?;
+        assert!(across_antimeridian.is_empty());
+
+        // box from 14 W eastwards around the globe to 24 W
+        let wrapped = msg.values_in_bbox(63.0, 67.0, -14.0, -24.0)?;
+        assert_eq!(wrapped.len(), 17 * 10);
+
+        let whole_globe = msg.values_in_bbox(-90.0, 90.0, -180.0, 180.0)?;
+        assert_eq!(whole_globe.len(), 17 * 49);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_field_record_json() -> Result<(), Box<dyn std::error::Error>> {
+        let file_path = Path::new("./data/iceland-surface.grib");
+        let mut handle = CodesHandle::new_from_file(file_path, ProductKind::GRIB)?;
+
+        while let Some(msg) = handle.next()? {
+            if msg.read_key_dynamic("shortName")? == DynamicKeyType::Str("2d".to_string()) {
+                let record = msg.to_field_record()?;
+
+                assert_eq!(record.values.shape(), [17, 49]);
+                assert_approx_eq!(f64, record.latitudes[0], 67.0);
+                assert_approx_eq!(f64, record.latitudes[16], 63.0);
+                assert_approx_eq!(f64, record.values[[0, 0]], 276.37793, epsilon = 0.000_1);
+
+                let json: serde_json::Value = serde_json::to_value(&record)?;
+
+                assert_eq!(json["units"], "K");
+                assert_eq!(json["short_name"], "2d");
+
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_lons_lats() -> Result<(), CodesError> {
         let file_path = Path::new("./data/iceland-surface.grib");